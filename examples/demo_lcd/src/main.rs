@@ -8,7 +8,7 @@ use embedded_hal::delay::DelayNs;
 
 use fugit::RateExtU32;
 use panic_halt as _;
-use st7789v2_driver::{ST7789V2, VERTICAL, HORIZONTAL}; // for using write! macro
+use st7789v2_driver::{Orientation, PanelConfig, ST7789V2Builder};
 
 use waveshare_rp2040_lcd_1_69::entry;
 use waveshare_rp2040_lcd_1_69::{
@@ -29,9 +29,6 @@ use embedded_graphics::{
     primitives::{Line, PrimitiveStyle, PrimitiveStyleBuilder, Rectangle},
 };
 
-const DEFAULT_LCD_WIDTH: u32 = 240;
-const DEFAULT_LCD_HEIGHT: u32 = 280;
-
 pub struct DelayWrapper<'a> {
     delay: &'a mut Delay,
 }
@@ -110,7 +107,7 @@ fn main() -> ! {
     let lcd_rst = pins
         .gp13
         .into_push_pull_output_in_state(hal::gpio::PinState::High);
-    let mut _lcd_bl = pins
+    let lcd_bl = pins
         .gp25
         .into_push_pull_output_in_state(hal::gpio::PinState::Low);
 
@@ -124,24 +121,16 @@ fn main() -> ! {
         embedded_hal::spi::MODE_0,
     );
 
-    let screen_direction = VERTICAL;
-    let mut lcd_width = DEFAULT_LCD_WIDTH;
-    let mut lcd_height = DEFAULT_LCD_HEIGHT;
-    if screen_direction == HORIZONTAL {
-        lcd_width = DEFAULT_LCD_HEIGHT;
-        lcd_height = DEFAULT_LCD_WIDTH;
-    }
     // Initialize the display
-    let mut display = ST7789V2::new(
-        spi_bus,
-        lcd_dc,
-        lcd_cs,
-        lcd_rst,
-        false,
-        screen_direction,
-        lcd_width,
-        lcd_height,
-    );
+    let mut display = ST7789V2Builder::new(spi_bus, lcd_dc, lcd_cs, lcd_rst)
+        .panel(PanelConfig::PANEL_240X280)
+        .orientation(Orientation::Portrait)
+        .backlight_pin(lcd_bl)
+        .defer_backlight(true)
+        .build()
+        .unwrap();
+    let lcd_width = display.size().width;
+    let lcd_height = display.size().height;
     //display.init(&mut delay).unwrap();
 
     //let mut delay = Delay::new(core.SYST, clocks.system_clock.freq().to_Hz());
@@ -150,9 +139,8 @@ fn main() -> ! {
     // Use the wrapper when initializing the display
     display.init(&mut delay_wrapper).unwrap();
 
-    // Clear the screen before turning on the backlight
+    // The backlight is turned on once the screen has been cleared
     display.clear(Rgb565::BLACK).unwrap();
-    _lcd_bl.into_push_pull_output_in_state(hal::gpio::PinState::High);
     delay.delay_ms(1000);
 
     let lcd_zero = Point::zero();
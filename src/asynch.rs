@@ -0,0 +1,974 @@
+use core::marker::PhantomData;
+
+use display_interface::{AsyncWriteOnlyDataCommand, DataFormat, DisplayError};
+use embedded_graphics::prelude::*;
+use embedded_hal::digital::OutputPin;
+use embedded_hal_async::delay::DelayNs;
+use embedded_hal_async::digital::Wait;
+use embedded_hal_async::spi::{SpiBus, SpiDevice};
+
+use crate::config::DisplayConfig;
+use crate::pixel_format::{fill_chunk, pack_chunk, CHUNK_SIZE};
+use crate::{
+    Backlight, ColorOrder, DataCommandInterface, DirtyRegions, Error, Instruction, Mirror, NoPin,
+    Orientation, PanelConfig, PixelFormat, Rgb565Format, ST7789V2Builder, SpiDeviceInterface,
+    SpiInterface, TearingEffect,
+};
+
+/// Async interface the display is connected to.
+///
+/// Implemented for [`SpiInterface`] over an async `SpiBus`, for [`SpiDeviceInterface`]
+/// over an async `SpiDevice` and for [`DataCommandInterface`] over any
+/// `AsyncWriteOnlyDataCommand`.
+#[allow(async_fn_in_trait)]
+pub trait AsyncInterface {
+    /// Error type of the SPI bus or device.
+    type SpiError;
+    /// Error type of the control pins.
+    type PinError;
+
+    /// Sends a command byte followed by its parameters.
+    ///
+    /// # Arguments
+    ///
+    /// * `command` - Command to write.
+    /// * `params` - Parameters for the command.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    async fn write_command(
+        &mut self,
+        command: u8,
+        params: &[u8],
+    ) -> Result<(), Error<Self::SpiError, Self::PinError>>;
+
+    /// Sends data following the last command, e.g. pixels after RAMWR.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - Data to write.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    async fn write_data(
+        &mut self,
+        data: &[u8],
+    ) -> Result<(), Error<Self::SpiError, Self::PinError>>;
+}
+
+impl<SPI, DC, CS, PinE> AsyncInterface for SpiInterface<SPI, DC, CS>
+where
+    SPI: SpiBus<u8>,
+    DC: OutputPin<Error = PinE>,
+    CS: OutputPin<Error = PinE>,
+{
+    type SpiError = SPI::Error;
+    type PinError = PinE;
+
+    async fn write_command(
+        &mut self,
+        command: u8,
+        params: &[u8],
+    ) -> Result<(), Error<SPI::Error, PinE>> {
+        self.dc.set_low().map_err(Error::Dc)?;
+        self.cs.set_low().map_err(Error::Cs)?;
+        self.spi.write(&[command]).await.map_err(Error::Spi)?;
+        if !params.is_empty() {
+            // DC must not change before the command byte has left the bus
+            self.spi.flush().await.map_err(Error::Spi)?;
+            self.dc.set_high().map_err(Error::Dc)?;
+            self.spi.write(params).await.map_err(Error::Spi)?;
+        }
+        self.spi.flush().await.map_err(Error::Spi)?;
+        self.cs.set_high().map_err(Error::Cs)?;
+        Ok(())
+    }
+
+    async fn write_data(&mut self, data: &[u8]) -> Result<(), Error<SPI::Error, PinE>> {
+        self.dc.set_high().map_err(Error::Dc)?;
+        self.cs.set_low().map_err(Error::Cs)?;
+        self.spi.write(data).await.map_err(Error::Spi)?;
+        self.spi.flush().await.map_err(Error::Spi)?;
+        self.cs.set_high().map_err(Error::Cs)?;
+        Ok(())
+    }
+}
+
+impl<SPI, DC> AsyncInterface for SpiDeviceInterface<SPI, DC>
+where
+    SPI: SpiDevice<u8>,
+    DC: OutputPin,
+{
+    type SpiError = SPI::Error;
+    type PinError = DC::Error;
+
+    async fn write_command(
+        &mut self,
+        command: u8,
+        params: &[u8],
+    ) -> Result<(), Error<SPI::Error, DC::Error>> {
+        // DC cannot change within a transaction, chip select is released before the
+        // parameters
+        self.dc.set_low().map_err(Error::Dc)?;
+        self.spi.write(&[command]).await.map_err(Error::Spi)?;
+        if !params.is_empty() {
+            self.dc.set_high().map_err(Error::Dc)?;
+            self.spi.write(params).await.map_err(Error::Spi)?;
+        }
+        Ok(())
+    }
+
+    async fn write_data(&mut self, data: &[u8]) -> Result<(), Error<SPI::Error, DC::Error>> {
+        self.dc.set_high().map_err(Error::Dc)?;
+        self.spi.write(data).await.map_err(Error::Spi)
+    }
+}
+
+impl<DI, PinE> AsyncInterface for DataCommandInterface<DI, PinE>
+where
+    DI: AsyncWriteOnlyDataCommand,
+{
+    type SpiError = DisplayError;
+    type PinError = PinE;
+
+    async fn write_command(
+        &mut self,
+        command: u8,
+        params: &[u8],
+    ) -> Result<(), Error<DisplayError, PinE>> {
+        self.di
+            .send_commands(DataFormat::U8(&[command]))
+            .await
+            .map_err(Error::Spi)?;
+        if !params.is_empty() {
+            self.di
+                .send_data(DataFormat::U8(params))
+                .await
+                .map_err(Error::Spi)?;
+        }
+        Ok(())
+    }
+
+    async fn write_data(&mut self, data: &[u8]) -> Result<(), Error<DisplayError, PinE>> {
+        self.di
+            .send_data(DataFormat::U8(data))
+            .await
+            .map_err(Error::Spi)
+    }
+}
+
+/// Async driver for the ST7789V2 display.
+///
+/// Use [`ST7789V2Builder::with_interface`] and `build_async` to create an instance.
+/// The driver offers the same configuration as the blocking [`ST7789V2`](crate::ST7789V2),
+/// but pushes frames without blocking the executor. A TE pin passed to the builder has
+/// to implement the async `Wait` trait. `DrawTarget` is a blocking trait and is not
+/// implemented; draw into a [`FrameBuffer`](crate::FrameBuffer) and push it with `show`.
+pub struct ST7789V2Async<DI, RST, PF = Rgb565Format, TE = NoPin, BL = NoPin> {
+    /// Interface the display is connected to.
+    interface: DI,
+
+    /// Reset pin.
+    rst: RST,
+
+    /// Tearing effect input pin.
+    te: TE,
+
+    /// Backlight of the module.
+    backlight: BL,
+
+    /// Panel configuration, orientation and derived geometry.
+    config: DisplayConfig,
+    _format: PhantomData<PF>,
+}
+
+impl<DI, RST, PF, TE, BL> ST7789V2Builder<DI, RST, PF, TE, BL>
+where
+    DI: AsyncInterface,
+    RST: OutputPin<Error = DI::PinError>,
+    PF: PixelFormat,
+{
+    /// Validates the configuration and creates the async driver.
+    ///
+    /// # Returns
+    ///
+    /// The driver, or `Error::InvalidGeometry` if the panel does not fit into the
+    /// frame memory of the controller.
+    #[allow(clippy::type_complexity)]
+    pub fn build_async(
+        self,
+    ) -> Result<ST7789V2Async<DI, RST, PF, TE, BL>, Error<DI::SpiError, DI::PinError>> {
+        let (interface, rst, te, backlight, config) = self.into_parts()?;
+
+        Ok(ST7789V2Async {
+            interface,
+            rst,
+            te,
+            backlight,
+            config,
+            _format: PhantomData,
+        })
+    }
+}
+
+impl<DI, RST, PF, TE, BL> ST7789V2Async<DI, RST, PF, TE, BL>
+where
+    DI: AsyncInterface,
+    RST: OutputPin<Error = DI::PinError>,
+    BL: Backlight<DI::PinError>,
+    PF: PixelFormat,
+{
+    /// Returns the configuration of the connected panel.
+    pub fn panel(&self) -> &PanelConfig {
+        &self.config.panel
+    }
+
+    /// Returns the current orientation of the display.
+    pub fn orientation(&self) -> Orientation {
+        self.config.orientation
+    }
+
+    /// Returns the mirroring applied on top of the orientation.
+    pub fn mirror(&self) -> Mirror {
+        self.config.mirror
+    }
+
+    /// Returns the color order of the panel.
+    pub fn color_order(&self) -> ColorOrder {
+        self.config.panel.color_order
+    }
+
+    /// Releases the interface, the reset and TE pins and the backlight.
+    pub fn release(self) -> (DI, RST, TE, BL) {
+        (self.interface, self.rst, self.te, self.backlight)
+    }
+
+    /// Changes the color order of the panel.
+    ///
+    /// # Arguments
+    ///
+    /// * `color_order` - The new color order.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub async fn set_color_order(
+        &mut self,
+        color_order: ColorOrder,
+    ) -> Result<(), Error<DI::SpiError, DI::PinError>> {
+        self.config.panel.color_order = color_order;
+        self.write_madctl().await
+    }
+
+    /// Changes the orientation of the display.
+    ///
+    /// # Arguments
+    ///
+    /// * `orientation` - The new orientation.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub async fn set_orientation(
+        &mut self,
+        orientation: Orientation,
+    ) -> Result<(), Error<DI::SpiError, DI::PinError>> {
+        self.config.orientation = orientation;
+        self.config.update_geometry();
+        self.write_madctl().await
+    }
+
+    /// Changes the mirroring applied on top of the orientation.
+    ///
+    /// # Arguments
+    ///
+    /// * `mirror` - The new mirroring.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub async fn set_mirror(
+        &mut self,
+        mirror: Mirror,
+    ) -> Result<(), Error<DI::SpiError, DI::PinError>> {
+        self.config.mirror = mirror;
+        self.config.update_geometry();
+        self.write_madctl().await
+    }
+
+    /// Sends MADCTL for the current orientation, mirroring and color order.
+    async fn write_madctl(&mut self) -> Result<(), Error<DI::SpiError, DI::PinError>> {
+        let madctl = self.config.madctl();
+        self.interface
+            .write_command(Instruction::MadCtl as u8, &[madctl])
+            .await
+    }
+
+    /// Returns the current backlight level in percent, 0 while it is off.
+    pub fn backlight(&self) -> u8 {
+        if self.config.backlight_on {
+            self.config.backlight_level
+        } else {
+            0
+        }
+    }
+
+    /// Turns the backlight on at the last level set.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn backlight_on(&mut self) -> Result<(), Error<DI::SpiError, DI::PinError>> {
+        self.backlight.set_percent(self.config.backlight_level)?;
+        self.config.backlight_on = true;
+        self.config.backlight_pending = false;
+        Ok(())
+    }
+
+    /// Turns the backlight off, keeping the level restored by `backlight_on`.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn backlight_off(&mut self) -> Result<(), Error<DI::SpiError, DI::PinError>> {
+        self.backlight.set_percent(0)?;
+        self.config.backlight_on = false;
+        self.config.backlight_pending = false;
+        Ok(())
+    }
+
+    /// Sets the backlight level.
+    ///
+    /// A level above 0% is also the level restored by `backlight_on`, 0% turns the
+    /// backlight off like `backlight_off`.
+    ///
+    /// # Arguments
+    ///
+    /// * `percent` - Backlight level (0 = off, 100 = full brightness).
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn set_backlight(&mut self, percent: u8) -> Result<(), Error<DI::SpiError, DI::PinError>> {
+        if percent == 0 {
+            return self.backlight_off();
+        }
+        self.config.backlight_level = percent.min(100);
+        self.backlight_on()
+    }
+
+    /// Fades the backlight from its current level to `target`.
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - Backlight level at the end of the fade (0 to 100).
+    /// * `duration_ms` - Duration of the fade in milliseconds.
+    /// * `delay` - Async delay provider.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub async fn fade_backlight<DELAY>(
+        &mut self,
+        target: u8,
+        duration_ms: u32,
+        delay: &mut DELAY,
+    ) -> Result<(), Error<DI::SpiError, DI::PinError>>
+    where
+        DELAY: DelayNs,
+    {
+        let target = target.min(100);
+        let start = self.backlight();
+        let steps = start.abs_diff(target) as u32;
+        if steps == 0 {
+            return self.set_backlight(target);
+        }
+
+        let step_us = duration_ms.saturating_mul(1000) / steps;
+        for step in 1..=steps {
+            let level = if target > start {
+                start + step as u8
+            } else {
+                start - step as u8
+            };
+            self.set_backlight(level)?;
+            delay.delay_us(step_us).await;
+        }
+
+        // Fading out keeps the level the fade started from for `backlight_on`
+        if target == 0 {
+            self.config.backlight_level = start;
+        }
+        Ok(())
+    }
+
+    /// Turns on a backlight held off by `init` once the first frame has been pushed.
+    fn frame_pushed(&mut self) -> Result<(), Error<DI::SpiError, DI::PinError>> {
+        if self.config.backlight_pending {
+            self.backlight_on()?;
+        }
+        Ok(())
+    }
+
+    /// Configures the tearing effect output of the controller.
+    ///
+    /// # Arguments
+    ///
+    /// * `mode` - The new TE output mode.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub async fn set_tearing_effect(
+        &mut self,
+        mode: TearingEffect,
+    ) -> Result<(), Error<DI::SpiError, DI::PinError>> {
+        let (command, params) = mode.command();
+        self.interface.write_command(command, params).await
+    }
+
+    /// Sets the scanline at which the TE signal is raised.
+    ///
+    /// # Arguments
+    ///
+    /// * `line` - Scanline in the frame memory (0 to 319).
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub async fn set_tear_scanline(
+        &mut self,
+        line: u16,
+    ) -> Result<(), Error<DI::SpiError, DI::PinError>> {
+        self.interface
+            .write_command(Instruction::Ste as u8, &line.to_be_bytes())
+            .await
+    }
+
+    /// Initializes the display.
+    ///
+    /// Sends the same sequence as the blocking driver: hardware reset, MADCTL, COLMOD,
+    /// the configured [`InitSequence`](crate::InitSequence), inversion, gamma curve,
+    /// frame rate, brightness, sleep out and display on.
+    ///
+    /// # Arguments
+    ///
+    /// * `delay` - Async delay provider.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub async fn init<DELAY>(
+        &mut self,
+        delay: &mut DELAY,
+    ) -> Result<(), Error<DI::SpiError, DI::PinError>>
+    where
+        DELAY: DelayNs,
+    {
+        if self.config.backlight_deferred {
+            // Hide the random frame memory content until the first frame is pushed
+            self.backlight.set_percent(0)?;
+            self.config.backlight_pending = true;
+        }
+        self.hard_reset(delay).await?;
+        self.write_madctl().await?;
+
+        self.interface
+            .write_command(Instruction::ColMod as u8, &[PF::COLMOD])
+            .await?;
+        for step in self.config.init_sequence.steps() {
+            self.interface
+                .write_command(step.command, step.params)
+                .await?;
+            if step.delay_ms > 0 {
+                delay.delay_ms(step.delay_ms).await;
+            }
+        }
+        let inversion = if self.config.panel.inverted {
+            Instruction::InvOn
+        } else {
+            Instruction::InvOff
+        };
+        self.interface.write_command(inversion as u8, &[]).await?;
+        if let Some(curve) = self.config.gamma_curve {
+            self.interface
+                .write_command(Instruction::GamSet as u8, &[curve.gamset()])
+                .await?;
+        }
+        if let Some(frame_rate) = self.config.frame_rate {
+            self.interface
+                .write_command(Instruction::PorCtrl as u8, &frame_rate.porch())
+                .await?;
+            self.interface
+                .write_command(Instruction::FrCtrl2 as u8, &[frame_rate.frctrl2()])
+                .await?;
+        }
+        if let Some(brightness) = self.config.brightness {
+            self.interface
+                .write_command(Instruction::WrDisBV as u8, &[brightness])
+                .await?;
+        }
+        self.interface
+            .write_command(Instruction::SlpOut as u8, &[])
+            .await?;
+
+        delay.delay_ms(120).await;
+
+        self.interface
+            .write_command(Instruction::DispOn as u8, &[])
+            .await?;
+
+        if !self.config.backlight_pending {
+            self.backlight_on()?;
+        }
+
+        Ok(())
+    }
+
+    /// Performs a hard reset of the display.
+    ///
+    /// # Arguments
+    ///
+    /// * `delay` - Async delay provider.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub async fn hard_reset<DELAY>(
+        &mut self,
+        delay: &mut DELAY,
+    ) -> Result<(), Error<DI::SpiError, DI::PinError>>
+    where
+        DELAY: DelayNs,
+    {
+        self.rst.set_high().map_err(Error::Rst)?;
+        delay.delay_ms(10).await;
+        self.rst.set_low().map_err(Error::Rst)?;
+        delay.delay_ms(10).await;
+        self.rst.set_high().map_err(Error::Rst)?;
+        delay.delay_ms(10).await;
+
+        Ok(())
+    }
+
+    /// Sets the address window and starts a memory write.
+    ///
+    /// # Arguments
+    ///
+    /// * `start_x` - Start x-coordinate.
+    /// * `start_y` - Start y-coordinate.
+    /// * `end_x` - End x-coordinate (inclusive).
+    /// * `end_y` - End y-coordinate (inclusive).
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure. `Error::OutOfBounds` is returned
+    /// if the end coordinates lie outside of the display.
+    pub async fn set_address_window(
+        &mut self,
+        start_x: u16,
+        start_y: u16,
+        end_x: u16,
+        end_y: u16,
+    ) -> Result<(), Error<DI::SpiError, DI::PinError>> {
+        if !self.config.contains(end_x, end_y) {
+            return Err(Error::OutOfBounds);
+        }
+
+        let (caset, raset) = self.config.address_window(start_x, start_y, end_x, end_y);
+        self.interface
+            .write_command(Instruction::CaSet as u8, &caset)
+            .await?;
+        self.interface
+            .write_command(Instruction::RaSet as u8, &raset)
+            .await?;
+        self.interface
+            .write_command(Instruction::RamWr as u8, &[])
+            .await
+    }
+
+    /// Streams pixels to the display.
+    ///
+    /// The address window must have been set up by the caller.
+    ///
+    /// # Arguments
+    ///
+    /// * `pixels` - Colors of the pixels in address window order.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    async fn write_pixels<I>(&mut self, pixels: I) -> Result<(), Error<DI::SpiError, DI::PinError>>
+    where
+        I: IntoIterator<Item = PF::Color>,
+    {
+        let mut chunk = [0u8; CHUNK_SIZE];
+        let mut pixels = pixels.into_iter();
+
+        loop {
+            let count = pack_chunk::<PF>(&mut chunk, &mut pixels);
+            if count == 0 {
+                return Ok(());
+            }
+            self.interface
+                .write_data(&chunk[..PF::buffer_size(count)])
+                .await?;
+        }
+    }
+
+    /// Streams `count` pixels of the same color to the display.
+    ///
+    /// The address window must have been set up by the caller.
+    ///
+    /// # Arguments
+    ///
+    /// * `color` - Color of the pixels.
+    /// * `count` - Number of pixels to write.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    async fn write_repeated(
+        &mut self,
+        color: PF::Color,
+        count: usize,
+    ) -> Result<(), Error<DI::SpiError, DI::PinError>> {
+        // Fill the chunk once and send it as often as needed
+        let mut chunk = [0u8; CHUNK_SIZE];
+        let capacity = fill_chunk::<PF>(&mut chunk, color, count);
+
+        let mut remaining = count;
+        while remaining > 0 {
+            let pixels = remaining.min(capacity);
+            self.interface
+                .write_data(&chunk[..PF::buffer_size(pixels)])
+                .await?;
+            remaining -= pixels;
+        }
+
+        Ok(())
+    }
+
+    /// Clears the screen by filling it with a single color.
+    ///
+    /// # Arguments
+    ///
+    /// * `color` - The color to fill the screen with.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub async fn clear_screen(
+        &mut self,
+        color: PF::Color,
+    ) -> Result<(), Error<DI::SpiError, DI::PinError>> {
+        let width = self.config.width as u16;
+        let height = self.config.height as u16;
+        self.set_address_window(0, 0, width - 1, height - 1).await?;
+        self.write_repeated(color, self.config.pixels()).await?;
+        self.frame_pushed()
+    }
+
+    /// Sets a pixel color at the given coordinates.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - X-coordinate.
+    /// * `y` - Y-coordinate.
+    /// * `color` - Color of the pixel.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure. `Error::OutOfBounds` is returned
+    /// if the pixel lies outside of the display.
+    pub async fn write_pixel(
+        &mut self,
+        x: u16,
+        y: u16,
+        color: PF::Color,
+    ) -> Result<(), Error<DI::SpiError, DI::PinError>> {
+        if !self.config.contains(x, y) {
+            return Err(Error::OutOfBounds);
+        }
+        self.set_address_window(x, y, x, y).await?;
+
+        let mut data = [0u8; 4];
+        PF::write_pixel(&mut data, 0, color);
+        self.interface
+            .write_data(&data[..PF::buffer_size(1)])
+            .await?;
+        self.frame_pushed()
+    }
+
+    /// Draws an image from a slice of raw pixel data.
+    ///
+    /// The image is packed in the driver's pixel format and has the dimensions of the
+    /// display; data past the last pixel of the display is ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `image_data` - Image data to draw.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub async fn draw_image(
+        &mut self,
+        image_data: &[u8],
+    ) -> Result<(), Error<DI::SpiError, DI::PinError>> {
+        let Some((_, _, end_x, end_y)) =
+            self.config
+                .clip_region(0, 0, self.config.width, self.config.height)
+        else {
+            return Ok(());
+        };
+        let size = PF::buffer_size(self.config.pixels_through(end_x, end_y));
+        if image_data.len() < size {
+            return Err(Error::BufferTooSmall);
+        }
+
+        self.set_address_window(0, 0, end_x, end_y).await?;
+        self.interface.write_data(&image_data[..size]).await?;
+        self.frame_pushed()
+    }
+
+    /// Displays the provided buffer on the screen.
+    ///
+    /// # Arguments
+    ///
+    /// * `buffer` - Buffer to display.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub async fn show(&mut self, buffer: &[u8]) -> Result<(), Error<DI::SpiError, DI::PinError>> {
+        let size = PF::buffer_size(self.config.pixels());
+        if buffer.len() < size {
+            return Err(Error::BufferTooSmall);
+        }
+
+        let width = self.config.width as u16;
+        let height = self.config.height as u16;
+        self.set_address_window(0, 0, width - 1, height - 1).await?;
+        self.interface.write_data(&buffer[..size]).await?;
+        self.frame_pushed()
+    }
+
+    /// Updates only the specified region of the display with the provided buffer.
+    ///
+    /// Parts of the region outside of the display are clipped, a region that lies completely
+    /// outside or has a zero size is ignored. The buffer holds the whole display, row by
+    /// row; it only has to extend up to the last pixel of the clipped region.
+    ///
+    /// # Arguments
+    ///
+    /// * `buffer` - A slice of bytes representing the pixel data in the driver's pixel format.
+    /// * `top_left_x` - The x-coordinate of the top-left corner of the region to update.
+    /// * `top_left_y` - The y-coordinate of the top-left corner of the region to update.
+    /// * `width` - The width of the region to update.
+    /// * `height` - The height of the region to update.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success (`Ok`) or failure (`Err`). `Error::BufferTooSmall`
+    /// is returned if the buffer does not cover the region.
+    pub async fn show_region(
+        &mut self,
+        buffer: &[u8],
+        top_left_x: u16,
+        top_left_y: u16,
+        width: u32,
+        height: u32,
+    ) -> Result<(), Error<DI::SpiError, DI::PinError>> {
+        // Clip the region to the display, nothing is left to draw if it lies outside
+        let Some((start_x, start_y, end_x, end_y)) = self
+            .config
+            .clip_region(top_left_x, top_left_y, width, height)
+        else {
+            return Ok(());
+        };
+        if buffer.len() < PF::buffer_size(self.config.pixels_through(end_x, end_y)) {
+            return Err(Error::BufferTooSmall);
+        }
+
+        self.set_address_window(start_x, start_y, end_x, end_y)
+            .await?;
+
+        if let Some(rows) = self
+            .config
+            .window_rows::<PF>(start_x, start_y, end_x, end_y)
+        {
+            for row in rows {
+                self.interface.write_data(&buffer[row]).await?;
+            }
+        } else {
+            // Rows of sub-byte formats do not start on a byte boundary, so the pixels
+            // are repacked into one continuous stream.
+            let pixels = self
+                .config
+                .window_pixels(start_x, start_y, end_x, end_y)
+                .map(|index| PF::read_pixel(buffer, index));
+            self.write_pixels(pixels).await?;
+        }
+
+        self.frame_pushed()
+    }
+
+    /// Updates the dirty regions of the display from the provided buffer.
+    ///
+    /// If the whole display is marked dirty, the buffer is shown completely.
+    ///
+    /// # Arguments
+    ///
+    /// * `buffer` - A slice of bytes representing the pixel data in the driver's pixel format.
+    /// * `regions` - The regions to update.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub async fn show_regions<const N: usize>(
+        &mut self,
+        buffer: &[u8],
+        regions: &DirtyRegions<N>,
+    ) -> Result<(), Error<DI::SpiError, DI::PinError>> {
+        if regions.is_full_screen() {
+            return self.show(buffer).await;
+        }
+        for region in regions.regions() {
+            self.show_region(buffer, region.x, region.y, region.width, region.height)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Updates the dirty regions of the display and clears them.
+    ///
+    /// # Arguments
+    ///
+    /// * `buffer` - A slice of bytes representing the pixel data in the driver's pixel format.
+    /// * `regions` - The regions to update, empty afterwards.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub async fn show_regions_and_clear<const N: usize>(
+        &mut self,
+        buffer: &[u8],
+        regions: &mut DirtyRegions<N>,
+    ) -> Result<(), Error<DI::SpiError, DI::PinError>> {
+        self.show_regions(buffer, regions).await?;
+        regions.clear();
+        Ok(())
+    }
+}
+
+impl<DI, RST, PF, TE, BL> ST7789V2Async<DI, RST, PF, TE, BL>
+where
+    DI: AsyncInterface,
+    RST: OutputPin<Error = DI::PinError>,
+    BL: Backlight<DI::PinError>,
+    TE: Wait<Error = DI::PinError>,
+    PF: PixelFormat,
+{
+    /// Waits for the next rising edge of the TE signal.
+    ///
+    /// The TE output has to be enabled with `set_tearing_effect` first.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub async fn wait_for_te(&mut self) -> Result<(), Error<DI::SpiError, DI::PinError>> {
+        self.te.wait_for_rising_edge().await.map_err(Error::Te)
+    }
+
+    /// Waits for the TE signal and displays the provided buffer.
+    ///
+    /// # Arguments
+    ///
+    /// * `buffer` - Buffer to display.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub async fn show_synced(
+        &mut self,
+        buffer: &[u8],
+    ) -> Result<(), Error<DI::SpiError, DI::PinError>> {
+        self.wait_for_te().await?;
+        self.show(buffer).await
+    }
+
+    /// Waits for the TE signal and updates the dirty regions from the provided buffer.
+    ///
+    /// # Arguments
+    ///
+    /// * `buffer` - A slice of bytes representing the pixel data in the driver's pixel format.
+    /// * `regions` - The regions to update.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub async fn show_regions_synced<const N: usize>(
+        &mut self,
+        buffer: &[u8],
+        regions: &DirtyRegions<N>,
+    ) -> Result<(), Error<DI::SpiError, DI::PinError>> {
+        self.wait_for_te().await?;
+        self.show_regions(buffer, regions).await
+    }
+}
+
+impl<DI, RST, PF, TE, BL> OriginDimensions for ST7789V2Async<DI, RST, PF, TE, BL> {
+    fn size(&self) -> Size {
+        Size::new(self.config.width, self.config.height)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{block_on, display_async};
+
+    const FRAME: usize = 240 * 280 * 2;
+
+    #[test]
+    fn show_region_matches_the_blocking_driver() {
+        let buffer = [0u8; FRAME];
+        let mut display = display_async(PanelConfig::PANEL_240X280);
+
+        // Clipped at the right and bottom edge, with the panel's row offset of 20
+        block_on(display.show_region(&buffer, 200, 250, 100, 100)).unwrap();
+        assert_eq!(display.interface.caset, [0, 200, 0, 239]);
+        assert_eq!(display.interface.raset, [1, 14, 1, 43]);
+        assert_eq!(display.interface.data_bytes, 40 * 30 * 2);
+        assert_eq!(display.interface.memory_writes, 1);
+
+        // Full width rows go out in one transfer
+        let mut display = display_async(PanelConfig::PANEL_240X280);
+        block_on(display.show_region(&buffer, 0, 10, 240, 5)).unwrap();
+        assert_eq!(display.interface.data_bytes, 240 * 5 * 2);
+
+        // Regions outside of the display are ignored, short buffers rejected
+        block_on(display.show_region(&buffer, 240, 0, 10, 10)).unwrap();
+        assert_eq!(display.interface.memory_writes, 1);
+        assert_eq!(
+            block_on(display.show_region(&buffer[..100], 0, 10, 10, 10)),
+            Err(Error::BufferTooSmall)
+        );
+    }
+
+    #[test]
+    fn draw_image_ignores_data_past_the_frame() {
+        let image = [0u8; FRAME + 100];
+        let mut display = display_async(PanelConfig::PANEL_240X280);
+        block_on(display.draw_image(&image)).unwrap();
+        assert_eq!(display.interface.data_bytes, FRAME);
+        assert_eq!(
+            block_on(display.write_pixel(240, 0, embedded_graphics::pixelcolor::Rgb565::RED)),
+            Err(Error::OutOfBounds)
+        );
+    }
+}
@@ -0,0 +1,161 @@
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::OutputPin;
+use embedded_hal::pwm::{Error as _, SetDutyCycle};
+
+use crate::{Error, Interface, NoPin, PixelFormat, ST7789V2};
+
+/// Backlight of the module, driven by the MCU.
+///
+/// Implemented for [`BacklightPin`] (on/off only), [`BacklightPwm`] (dimmable) and
+/// [`NoPin`] for modules without a controllable backlight.
+pub trait Backlight<PinE> {
+    /// Sets the backlight to `percent` percent (0 = off, 100 = full brightness).
+    fn set_percent<SpiE>(&mut self, percent: u8) -> Result<(), Error<SpiE, PinE>>;
+}
+
+/// Backlight switched by a GPIO, any level above 0% turns it on.
+pub struct BacklightPin<P>(pub P);
+
+/// Backlight dimmed by a PWM channel.
+pub struct BacklightPwm<P>(pub P);
+
+impl<PinE> Backlight<PinE> for NoPin {
+    fn set_percent<SpiE>(&mut self, _percent: u8) -> Result<(), Error<SpiE, PinE>> {
+        Ok(())
+    }
+}
+
+impl<P: OutputPin> Backlight<P::Error> for BacklightPin<P> {
+    fn set_percent<SpiE>(&mut self, percent: u8) -> Result<(), Error<SpiE, P::Error>> {
+        if percent > 0 {
+            self.0.set_high().map_err(Error::Backlight)
+        } else {
+            self.0.set_low().map_err(Error::Backlight)
+        }
+    }
+}
+
+impl<P: SetDutyCycle, PinE> Backlight<PinE> for BacklightPwm<P> {
+    fn set_percent<SpiE>(&mut self, percent: u8) -> Result<(), Error<SpiE, PinE>> {
+        self.0
+            .set_duty_cycle_percent(percent.min(100))
+            .map_err(|e| Error::Pwm(e.kind()))
+    }
+}
+
+impl<DI, RST, PF, TE, BL> ST7789V2<DI, RST, PF, TE, BL>
+where
+    DI: Interface,
+    RST: OutputPin<Error = DI::PinError>,
+    BL: Backlight<DI::PinError>,
+    PF: PixelFormat,
+{
+    /// Returns the current backlight level in percent, 0 while it is off.
+    pub fn backlight(&self) -> u8 {
+        if self.config.backlight_on {
+            self.config.backlight_level
+        } else {
+            0
+        }
+    }
+
+    /// Turns the backlight on at the last level set.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn backlight_on(&mut self) -> Result<(), Error<DI::SpiError, DI::PinError>> {
+        self.backlight.set_percent(self.config.backlight_level)?;
+        self.config.backlight_on = true;
+        self.config.backlight_pending = false;
+        Ok(())
+    }
+
+    /// Turns the backlight off, keeping the level restored by `backlight_on`.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn backlight_off(&mut self) -> Result<(), Error<DI::SpiError, DI::PinError>> {
+        self.backlight.set_percent(0)?;
+        self.config.backlight_on = false;
+        self.config.backlight_pending = false;
+        Ok(())
+    }
+
+    /// Sets the backlight level.
+    ///
+    /// A level above 0% is also the level restored by `backlight_on`, 0% turns the
+    /// backlight off like `backlight_off`.
+    ///
+    /// # Arguments
+    ///
+    /// * `percent` - Backlight level (0 = off, 100 = full brightness).
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn set_backlight(&mut self, percent: u8) -> Result<(), Error<DI::SpiError, DI::PinError>> {
+        if percent == 0 {
+            return self.backlight_off();
+        }
+        self.config.backlight_level = percent.min(100);
+        self.backlight_on()
+    }
+
+    /// Fades the backlight from its current level to `target`.
+    ///
+    /// The level is changed in steps of one percent, spread evenly over `duration_ms`.
+    /// A GPIO backlight simply switches once the level crosses 0%.
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - Backlight level at the end of the fade (0 to 100).
+    /// * `duration_ms` - Duration of the fade in milliseconds.
+    /// * `delay` - Delay provider.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn fade_backlight<DELAY>(
+        &mut self,
+        target: u8,
+        duration_ms: u32,
+        delay: &mut DELAY,
+    ) -> Result<(), Error<DI::SpiError, DI::PinError>>
+    where
+        DELAY: DelayNs,
+    {
+        let target = target.min(100);
+        let start = self.backlight();
+        let steps = start.abs_diff(target) as u32;
+        if steps == 0 {
+            return self.set_backlight(target);
+        }
+
+        let step_us = duration_ms.saturating_mul(1000) / steps;
+        for step in 1..=steps {
+            let level = if target > start {
+                start + step as u8
+            } else {
+                start - step as u8
+            };
+            self.set_backlight(level)?;
+            delay.delay_us(step_us);
+        }
+
+        // Fading out keeps the level the fade started from for `backlight_on`
+        if target == 0 {
+            self.config.backlight_level = start;
+        }
+        Ok(())
+    }
+
+    /// Turns on a backlight held off by `init` once the first frame has been pushed.
+    pub(crate) fn frame_pushed(&mut self) -> Result<(), Error<DI::SpiError, DI::PinError>> {
+        if self.config.backlight_pending {
+            self.backlight_on()?;
+        }
+        Ok(())
+    }
+}
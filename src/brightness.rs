@@ -0,0 +1,118 @@
+use embedded_hal::digital::OutputPin;
+
+use crate::{Backlight, Error, Instruction, Interface, PixelFormat, ST7789V2};
+
+/// Content adaptive brightness control (CABC) mode.
+///
+/// CABC lowers the backlight for dark content and compensates by brightening the
+/// pixels, which saves power on modules whose LED driver is controlled by the ST7789V2.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum CabcMode {
+    /// CABC disabled.
+    #[default]
+    Off,
+    /// Optimized for user interfaces, the backlight is barely reduced.
+    UserInterface,
+    /// Optimized for still pictures.
+    StillPicture,
+    /// Optimized for video, the backlight is reduced the most.
+    MovingImage,
+}
+
+impl<DI, RST, PF, TE, BL> ST7789V2<DI, RST, PF, TE, BL>
+where
+    DI: Interface,
+    RST: OutputPin<Error = DI::PinError>,
+    BL: Backlight<DI::PinError>,
+    PF: PixelFormat,
+{
+    /// Sets the display brightness.
+    ///
+    /// This only has an effect on modules whose backlight driver is controlled by the
+    /// ST7789V2 and once the brightness control block has been enabled with
+    /// `set_display_control`. The value is also written by later calls to `init`.
+    ///
+    /// # Arguments
+    ///
+    /// * `brightness` - Brightness (0 = lowest, 255 = highest).
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn set_brightness(
+        &mut self,
+        brightness: u8,
+    ) -> Result<(), Error<DI::SpiError, DI::PinError>> {
+        self.write_command(Instruction::WrDisBV as u8, &[brightness])?;
+        self.config.brightness = Some(brightness);
+        Ok(())
+    }
+
+    /// Configures the brightness control block (WRCTRLD).
+    ///
+    /// # Arguments
+    ///
+    /// * `brightness_ctrl` - Enables the brightness control block.
+    /// * `dimming` - Fades between brightness levels instead of switching at once.
+    /// * `backlight` - Turns the backlight control output on.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn set_display_control(
+        &mut self,
+        brightness_ctrl: bool,
+        dimming: bool,
+        backlight: bool,
+    ) -> Result<(), Error<DI::SpiError, DI::PinError>> {
+        let mut ctrl = 0;
+        if brightness_ctrl {
+            ctrl |= 0x20;
+        }
+        if dimming {
+            ctrl |= 0x08;
+        }
+        if backlight {
+            ctrl |= 0x04;
+        }
+        self.write_command(Instruction::WrCtrLD as u8, &[ctrl])
+    }
+
+    /// Selects the content adaptive brightness control mode.
+    ///
+    /// # Arguments
+    ///
+    /// * `mode` - The new CABC mode.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn set_cabc_mode(
+        &mut self,
+        mode: CabcMode,
+    ) -> Result<(), Error<DI::SpiError, DI::PinError>> {
+        let value = match mode {
+            CabcMode::Off => 0x00,
+            CabcMode::UserInterface => 0x01,
+            CabcMode::StillPicture => 0x02,
+            CabcMode::MovingImage => 0x03,
+        };
+        self.write_command(Instruction::WrCACE as u8, &[value])
+    }
+
+    /// Sets the lowest brightness CABC may reduce the backlight to.
+    ///
+    /// # Arguments
+    ///
+    /// * `brightness` - Minimum brightness (0 = lowest, 255 = highest).
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn set_cabc_min_brightness(
+        &mut self,
+        brightness: u8,
+    ) -> Result<(), Error<DI::SpiError, DI::PinError>> {
+        self.write_command(Instruction::WrCABCMB as u8, &[brightness])
+    }
+}
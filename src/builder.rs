@@ -0,0 +1,288 @@
+use core::marker::PhantomData;
+
+use embedded_hal::digital::OutputPin;
+
+use crate::config::DisplayConfig;
+use crate::{
+    BacklightPin, BacklightPwm, ColorOrder, Error, InitSequence, Interface, Mirror, NoPin,
+    Orientation, PanelConfig, PixelFormat, Rgb565Format, SpiInterface, ST7789V2,
+};
+
+/// Builder for the [`ST7789V2`] driver.
+///
+/// Only the SPI bus and the control pins are required. Everything else defaults to
+/// the Waveshare 1.69" 240x280 module in portrait orientation using RGB565.
+///
+/// `new` takes an `SpiBus` whose chip select is driven by the driver. On a bus shared
+/// with other devices, pass an [`SpiDeviceInterface`](crate::SpiDeviceInterface) to
+/// [`with_interface`](Self::with_interface) instead.
+///
+/// `build` creates the blocking driver. With the `async` feature enabled, a builder
+/// around an async interface can create an `ST7789V2Async` through `build_async` instead.
+///
+/// ```ignore
+/// let mut display = ST7789V2Builder::new(spi, dc, cs, rst)
+///     .panel(PanelConfig::PANEL_240X240)
+///     .orientation(Orientation::Landscape)
+///     .build()?;
+/// display.init(&mut delay)?;
+///
+/// // On a shared bus, e.g. with `embedded_hal_bus::spi::RefCellDevice`
+/// let interface = SpiDeviceInterface::new(spi_device, dc);
+/// let mut display = ST7789V2Builder::with_interface(interface, rst).build()?;
+/// ```
+pub struct ST7789V2Builder<DI, RST, PF = Rgb565Format, TE = NoPin, BL = NoPin> {
+    interface: DI,
+    rst: RST,
+    te: TE,
+    backlight: BL,
+    defer_backlight: bool,
+    backlight_level: u8,
+    panel: PanelConfig,
+    orientation: Orientation,
+    mirror: Mirror,
+    color_order: Option<ColorOrder>,
+    inverted: Option<bool>,
+    brightness: Option<u8>,
+    init_sequence: InitSequence,
+    _format: PhantomData<PF>,
+}
+
+impl<SPI, DC, CS, RST> ST7789V2Builder<SpiInterface<SPI, DC, CS>, RST> {
+    /// Creates a new builder for a display on an SPI bus.
+    ///
+    /// # Arguments
+    ///
+    /// * `spi` - SPI interface.
+    /// * `dc` - Data/command pin.
+    /// * `cs` - Chip select pin.
+    /// * `rst` - Reset pin.
+    pub fn new(spi: SPI, dc: DC, cs: CS, rst: RST) -> Self {
+        Self::with_interface(SpiInterface::new(spi, dc, cs), rst)
+    }
+}
+
+impl<DI, RST> ST7789V2Builder<DI, RST> {
+    /// Creates a new builder for a display on the given interface.
+    ///
+    /// # Arguments
+    ///
+    /// * `interface` - Interface the display is connected to.
+    /// * `rst` - Reset pin.
+    pub fn with_interface(interface: DI, rst: RST) -> Self {
+        ST7789V2Builder {
+            interface,
+            rst,
+            te: NoPin,
+            backlight: NoPin,
+            defer_backlight: false,
+            backlight_level: 100,
+            panel: PanelConfig::default(),
+            orientation: Orientation::default(),
+            mirror: Mirror::NONE,
+            color_order: None,
+            inverted: None,
+            brightness: None,
+            init_sequence: InitSequence::default(),
+            _format: PhantomData,
+        }
+    }
+}
+
+impl<DI, RST, PF: PixelFormat, TE, BL> ST7789V2Builder<DI, RST, PF, TE, BL> {
+    /// Sets the pixel format used on the interface, e.g. `Rgb666Format`.
+    pub fn pixel_format<F: PixelFormat>(self, _format: F) -> ST7789V2Builder<DI, RST, F, TE, BL> {
+        ST7789V2Builder {
+            interface: self.interface,
+            rst: self.rst,
+            te: self.te,
+            backlight: self.backlight,
+            defer_backlight: self.defer_backlight,
+            backlight_level: self.backlight_level,
+            panel: self.panel,
+            orientation: self.orientation,
+            mirror: self.mirror,
+            color_order: self.color_order,
+            inverted: self.inverted,
+            brightness: self.brightness,
+            init_sequence: self.init_sequence,
+            _format: PhantomData,
+        }
+    }
+
+    /// Sets the tearing effect (TE) input pin.
+    ///
+    /// The pin enables the `show_synced` and `show_regions_synced` methods, which wait
+    /// for the TE signal before streaming a frame. It has to share the error type of the
+    /// other control pins.
+    pub fn te_pin<T>(self, te: T) -> ST7789V2Builder<DI, RST, PF, T, BL> {
+        ST7789V2Builder {
+            interface: self.interface,
+            rst: self.rst,
+            te,
+            backlight: self.backlight,
+            defer_backlight: self.defer_backlight,
+            backlight_level: self.backlight_level,
+            panel: self.panel,
+            orientation: self.orientation,
+            mirror: self.mirror,
+            color_order: self.color_order,
+            inverted: self.inverted,
+            brightness: self.brightness,
+            init_sequence: self.init_sequence,
+            _format: PhantomData,
+        }
+    }
+
+    /// Sets a backlight switched on and off by a GPIO.
+    pub fn backlight_pin<P>(self, pin: P) -> ST7789V2Builder<DI, RST, PF, TE, BacklightPin<P>> {
+        self.backlight_driver(BacklightPin(pin))
+    }
+
+    /// Sets a backlight dimmed by a PWM channel.
+    pub fn backlight_pwm<P>(self, pwm: P) -> ST7789V2Builder<DI, RST, PF, TE, BacklightPwm<P>> {
+        self.backlight_driver(BacklightPwm(pwm))
+    }
+
+    /// Sets the backlight of the module.
+    fn backlight_driver<B>(self, backlight: B) -> ST7789V2Builder<DI, RST, PF, TE, B> {
+        ST7789V2Builder {
+            interface: self.interface,
+            rst: self.rst,
+            te: self.te,
+            backlight,
+            defer_backlight: self.defer_backlight,
+            backlight_level: self.backlight_level,
+            panel: self.panel,
+            orientation: self.orientation,
+            mirror: self.mirror,
+            color_order: self.color_order,
+            inverted: self.inverted,
+            brightness: self.brightness,
+            init_sequence: self.init_sequence,
+            _format: PhantomData,
+        }
+    }
+
+    /// Sets the backlight level in percent `init` turns the backlight on at (default 100).
+    pub fn backlight_level(mut self, percent: u8) -> Self {
+        self.backlight_level = percent.min(100);
+        self
+    }
+
+    /// Keeps the backlight off after `init` until the first frame has been pushed.
+    ///
+    /// The frame memory holds random data after power-up. With this option the backlight
+    /// is only turned on once the first pixels have been written, so the garbage is
+    /// never visible.
+    pub fn defer_backlight(mut self, defer: bool) -> Self {
+        self.defer_backlight = defer;
+        self
+    }
+
+    /// Sets the geometry of the connected panel.
+    pub fn panel(mut self, panel: PanelConfig) -> Self {
+        self.panel = panel;
+        self
+    }
+
+    /// Sets the initial orientation.
+    pub fn orientation(mut self, orientation: Orientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
+    /// Sets the initial mirroring.
+    pub fn mirror(mut self, mirror: Mirror) -> Self {
+        self.mirror = mirror;
+        self
+    }
+
+    /// Overrides the color order of the panel preset.
+    pub fn color_order(mut self, color_order: ColorOrder) -> Self {
+        self.color_order = Some(color_order);
+        self
+    }
+
+    /// Overrides the inversion default of the panel preset.
+    pub fn inverted(mut self, inverted: bool) -> Self {
+        self.inverted = Some(inverted);
+        self
+    }
+
+    /// Sets the display brightness written during `init` (0 = lowest, 255 = highest).
+    ///
+    /// This only has an effect on modules whose backlight driver is controlled by the
+    /// ST7789V2. If unset, the controller's reset value is kept.
+    pub fn brightness(mut self, brightness: u8) -> Self {
+        self.brightness = Some(brightness);
+        self
+    }
+
+    /// Sets the vendor tuning commands sent during `init`.
+    pub fn init_sequence(mut self, init_sequence: InitSequence) -> Self {
+        self.init_sequence = init_sequence;
+        self
+    }
+
+    /// Validates the configuration.
+    ///
+    /// # Returns
+    ///
+    /// The interface, the reset and TE pins, the backlight and the configuration, or
+    /// `Error::InvalidGeometry` if the panel does not fit into the frame memory of the
+    /// controller.
+    #[allow(clippy::type_complexity)]
+    pub(crate) fn into_parts<SpiE, PinE>(
+        self,
+    ) -> Result<(DI, RST, TE, BL, DisplayConfig), Error<SpiE, PinE>> {
+        let mut panel = self.panel;
+        if let Some(color_order) = self.color_order {
+            panel.color_order = color_order;
+        }
+        if let Some(inverted) = self.inverted {
+            panel.inverted = inverted;
+        }
+        if !panel.is_valid() {
+            return Err(Error::InvalidGeometry);
+        }
+
+        let mut config = DisplayConfig::new(
+            panel,
+            self.orientation,
+            self.mirror,
+            self.brightness,
+            self.init_sequence,
+        );
+        config.backlight_deferred = self.defer_backlight;
+        config.backlight_level = self.backlight_level;
+        Ok((self.interface, self.rst, self.te, self.backlight, config))
+    }
+}
+
+impl<DI, RST, PF, TE, BL> ST7789V2Builder<DI, RST, PF, TE, BL>
+where
+    DI: Interface,
+    RST: OutputPin<Error = DI::PinError>,
+    PF: PixelFormat,
+{
+    /// Validates the configuration and creates the driver.
+    ///
+    /// # Returns
+    ///
+    /// The driver, or `Error::InvalidGeometry` if the panel does not fit into the
+    /// frame memory of the controller.
+    #[allow(clippy::type_complexity)]
+    pub fn build(self) -> Result<ST7789V2<DI, RST, PF, TE, BL>, Error<DI::SpiError, DI::PinError>> {
+        let (interface, rst, te, backlight, config) = self.into_parts()?;
+
+        Ok(ST7789V2 {
+            interface,
+            rst,
+            te,
+            backlight,
+            config,
+            _format: PhantomData,
+        })
+    }
+}
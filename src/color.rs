@@ -0,0 +1,105 @@
+use embedded_hal::digital::OutputPin;
+
+use crate::{Backlight, Error, Instruction, Interface, PixelFormat, ST7789V2};
+
+/// Predefined gamma curve selected with GAMSET.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum GammaCurve {
+    /// Gamma curve 1 (G2.2), the reset default.
+    #[default]
+    Gamma1,
+    /// Gamma curve 2 (G1.8).
+    Gamma2,
+    /// Gamma curve 3 (G2.5).
+    Gamma3,
+    /// Gamma curve 4 (G1.0).
+    Gamma4,
+}
+
+impl GammaCurve {
+    /// Returns the GAMSET parameter.
+    pub(crate) fn gamset(self) -> u8 {
+        match self {
+            GammaCurve::Gamma1 => 0x01,
+            GammaCurve::Gamma2 => 0x02,
+            GammaCurve::Gamma3 => 0x04,
+            GammaCurve::Gamma4 => 0x08,
+        }
+    }
+}
+
+impl<DI, RST, PF, TE, BL> ST7789V2<DI, RST, PF, TE, BL>
+where
+    DI: Interface,
+    RST: OutputPin<Error = DI::PinError>,
+    BL: Backlight<DI::PinError>,
+    PF: PixelFormat,
+{
+    /// Enables or disables display inversion.
+    ///
+    /// Most IPS panels need inversion enabled to show correct colors. The setting is
+    /// kept for later calls to `init`.
+    ///
+    /// # Arguments
+    ///
+    /// * `inverted` - Whether the colors are inverted.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn set_inversion(
+        &mut self,
+        inverted: bool,
+    ) -> Result<(), Error<DI::SpiError, DI::PinError>> {
+        let command = if inverted {
+            Instruction::InvOn
+        } else {
+            Instruction::InvOff
+        };
+        self.write_command(command as u8, &[])?;
+        self.config.panel.inverted = inverted;
+        Ok(())
+    }
+
+    /// Selects one of the predefined gamma curves.
+    ///
+    /// The setting is kept for later calls to `init`.
+    ///
+    /// # Arguments
+    ///
+    /// * `curve` - The gamma curve.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn set_gamma_curve(
+        &mut self,
+        curve: GammaCurve,
+    ) -> Result<(), Error<DI::SpiError, DI::PinError>> {
+        self.write_command(Instruction::GamSet as u8, &[curve.gamset()])?;
+        self.config.gamma_curve = Some(curve);
+        Ok(())
+    }
+
+    /// Sets custom gamma correction tables.
+    ///
+    /// The tables use the PVGAMCTRL/NVGAMCTRL register layout of the datasheet, e.g.
+    /// the values of the panel vendor's init code.
+    ///
+    /// # Arguments
+    ///
+    /// * `positive` - Gamma values for the positive source voltage (PVGAMCTRL).
+    /// * `negative` - Gamma values for the negative source voltage (NVGAMCTRL).
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn set_custom_gamma(
+        &mut self,
+        positive: &[u8; 14],
+        negative: &[u8; 14],
+    ) -> Result<(), Error<DI::SpiError, DI::PinError>> {
+        self.write_command(Instruction::PvGamCtrl as u8, positive)?;
+        self.write_command(Instruction::NvGamCtrl as u8, negative)
+    }
+}
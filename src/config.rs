@@ -0,0 +1,308 @@
+use core::ops::Range;
+
+use crate::orientation::MADCTL_MY;
+use crate::{
+    FrameRate, GammaCurve, InitSequence, Mirror, Orientation, PanelConfig, PixelFormat, PowerState,
+};
+
+/// Configuration and derived geometry shared by the blocking and async drivers.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct DisplayConfig {
+    /// Geometry and defaults of the connected panel.
+    pub(crate) panel: PanelConfig,
+    /// Current orientation of the display content.
+    pub(crate) orientation: Orientation,
+    /// Mirroring applied on top of the orientation.
+    pub(crate) mirror: Mirror,
+    /// Display brightness written during `init`, if any.
+    pub(crate) brightness: Option<u8>,
+    /// Gamma curve written during `init`, if any.
+    pub(crate) gamma_curve: Option<GammaCurve>,
+    /// Frame rate written during `init`, if any.
+    pub(crate) frame_rate: Option<FrameRate>,
+    /// Vendor tuning commands sent during `init`.
+    pub(crate) init_sequence: InitSequence,
+
+    /// Size of the display in the current orientation.
+    pub(crate) width: u32,
+    pub(crate) height: u32,
+    /// Offset of the visible area in the frame memory in the current orientation.
+    pub(crate) x_offset: u16,
+    pub(crate) y_offset: u16,
+
+    /// Power modes the controller has been switched to.
+    pub(crate) power: PowerState,
+
+    /// Backlight level in percent restored when the backlight is turned on.
+    pub(crate) backlight_level: u8,
+    /// The backlight has been turned on.
+    pub(crate) backlight_on: bool,
+    /// Keep the backlight off after `init` until the first frame has been pushed.
+    pub(crate) backlight_deferred: bool,
+    /// The backlight is held off until the next frame.
+    pub(crate) backlight_pending: bool,
+
+    /// First panel row and number of rows of the vertical scrolling area.
+    pub(crate) scroll_top: u16,
+    pub(crate) scroll_height: u16,
+    /// Current scroll offset within the scrolling area.
+    pub(crate) scroll_offset: u16,
+}
+
+impl DisplayConfig {
+    /// Creates a configuration and computes the geometry for the given orientation.
+    pub(crate) fn new(
+        panel: PanelConfig,
+        orientation: Orientation,
+        mirror: Mirror,
+        brightness: Option<u8>,
+        init_sequence: InitSequence,
+    ) -> Self {
+        let mut config = DisplayConfig {
+            panel,
+            orientation,
+            mirror,
+            brightness,
+            gamma_curve: None,
+            frame_rate: None,
+            init_sequence,
+            width: 0,
+            height: 0,
+            x_offset: 0,
+            y_offset: 0,
+            power: PowerState::default(),
+            backlight_level: 100,
+            backlight_on: false,
+            backlight_deferred: false,
+            backlight_pending: false,
+            scroll_top: 0,
+            scroll_height: 0,
+            scroll_offset: 0,
+        };
+        config.update_geometry();
+        config
+    }
+
+    /// Returns MADCTL for the current orientation, mirroring and color order.
+    pub(crate) fn madctl(&self) -> u8 {
+        self.orientation.madctl(self.mirror) | self.panel.color_order.madctl()
+    }
+
+    /// Recomputes the size and frame memory offsets for the current orientation.
+    pub(crate) fn update_geometry(&mut self) {
+        let (width, height) = self.panel.size(self.orientation);
+        self.width = width as u32;
+        self.height = height as u32;
+        (self.x_offset, self.y_offset) = self.panel.offsets(self.orientation.madctl(self.mirror));
+    }
+
+    /// Returns the first visible frame memory row for the current row address order.
+    ///
+    /// Rows are counted as seen in `Orientation::Portrait`. With MY set the visible area
+    /// sits at the other end of the frame memory.
+    pub(crate) fn row_offset(&self) -> u16 {
+        if self.madctl() & MADCTL_MY != 0 {
+            self.panel.row_offset_flipped
+        } else {
+            self.panel.row_offset
+        }
+    }
+
+    /// Returns the number of pixels of the whole display.
+    pub(crate) fn pixels(&self) -> usize {
+        (self.width * self.height) as usize
+    }
+
+    /// Clips a region to the display.
+    ///
+    /// Returns the inclusive start and end coordinates of the visible part of the region,
+    /// or `None` if the region is empty or lies completely outside of the display.
+    pub(crate) fn clip_region(
+        &self,
+        x: u16,
+        y: u16,
+        width: u32,
+        height: u32,
+    ) -> Option<(u16, u16, u16, u16)> {
+        if width == 0 || height == 0 || x as u32 >= self.width || y as u32 >= self.height {
+            return None;
+        }
+        let end_x = (x as u32).saturating_add(width).min(self.width) - 1;
+        let end_y = (y as u32).saturating_add(height).min(self.height) - 1;
+        Some((x, y, end_x as u16, end_y as u16))
+    }
+
+    /// Returns the number of pixels a display sized buffer needs to cover the pixel
+    /// at the given coordinates.
+    pub(crate) fn pixels_through(&self, x: u16, y: u16) -> usize {
+        y as usize * self.width as usize + x as usize + 1
+    }
+
+    /// Returns the byte ranges of a display sized buffer covering the given window.
+    ///
+    /// Rows spanning the whole display are contiguous in the buffer and returned as a
+    /// single range, otherwise there is one range per row. Returns `None` for formats
+    /// whose rows may start in the middle of a byte.
+    pub(crate) fn window_rows<PF: PixelFormat>(
+        &self,
+        start_x: u16,
+        start_y: u16,
+        end_x: u16,
+        end_y: u16,
+    ) -> Option<impl Iterator<Item = Range<usize>>> {
+        if !PF::BITS_PER_PIXEL.is_multiple_of(8) {
+            return None;
+        }
+        let bytes_per_pixel = PF::BITS_PER_PIXEL / 8;
+        let stride = self.width as usize * bytes_per_pixel;
+        let width = (end_x - start_x + 1) as usize * bytes_per_pixel;
+        let rows = (end_y - start_y + 1) as usize;
+        let start = start_y as usize * stride + start_x as usize * bytes_per_pixel;
+
+        let (len, count) = if width == stride {
+            (rows * stride, 1)
+        } else {
+            (width, rows)
+        };
+        Some((0..count).map(move |row| start + row * stride..start + row * stride + len))
+    }
+
+    /// Returns the indices of the pixels of a display sized buffer in the given window,
+    /// row by row.
+    pub(crate) fn window_pixels(
+        &self,
+        start_x: u16,
+        start_y: u16,
+        end_x: u16,
+        end_y: u16,
+    ) -> impl Iterator<Item = usize> {
+        let buffer_width = self.width as usize;
+        (start_y as usize..=end_y as usize).flat_map(move |y| {
+            let row_start = y * buffer_width;
+            row_start + start_x as usize..=row_start + end_x as usize
+        })
+    }
+
+    /// Returns `true` if the pixel at the given coordinates lies on the display.
+    pub(crate) fn contains(&self, x: u16, y: u16) -> bool {
+        (x as u32) < self.width && (y as u32) < self.height
+    }
+
+    /// Returns the CASET and RASET parameters for the given window (inclusive ends).
+    pub(crate) fn address_window(
+        &self,
+        start_x: u16,
+        start_y: u16,
+        end_x: u16,
+        end_y: u16,
+    ) -> ([u8; 4], [u8; 4]) {
+        // Translate to frame memory coordinates for the current orientation
+        let [xs_hi, xs_lo] = (start_x + self.x_offset).to_be_bytes();
+        let [xe_hi, xe_lo] = (end_x + self.x_offset).to_be_bytes();
+        let [ys_hi, ys_lo] = (start_y + self.y_offset).to_be_bytes();
+        let [ye_hi, ye_lo] = (end_y + self.y_offset).to_be_bytes();
+
+        ([xs_hi, xs_lo, xe_hi, xe_lo], [ys_hi, ys_lo, ye_hi, ye_lo])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> DisplayConfig {
+        DisplayConfig::new(
+            PanelConfig::PANEL_240X280,
+            Orientation::Portrait,
+            Mirror::NONE,
+            None,
+            InitSequence::WAVESHARE_1_69,
+        )
+    }
+
+    #[test]
+    fn clip_region_inside() {
+        assert_eq!(config().clip_region(10, 20, 30, 40), Some((10, 20, 39, 59)));
+    }
+
+    #[test]
+    fn clip_region_full_display() {
+        assert_eq!(config().clip_region(0, 0, 240, 280), Some((0, 0, 239, 279)));
+    }
+
+    #[test]
+    fn clip_region_right_and_bottom_edge() {
+        assert_eq!(
+            config().clip_region(200, 250, 100, 100),
+            Some((200, 250, 239, 279))
+        );
+        assert_eq!(
+            config().clip_region(239, 279, 1, 1),
+            Some((239, 279, 239, 279))
+        );
+    }
+
+    #[test]
+    fn clip_region_does_not_overflow() {
+        assert_eq!(config().clip_region(u16::MAX - 1, 0, u32::MAX, 1), None);
+        assert_eq!(
+            config().clip_region(10, 10, u32::MAX, u32::MAX),
+            Some((10, 10, 239, 279))
+        );
+    }
+
+    #[test]
+    fn clip_region_outside() {
+        assert_eq!(config().clip_region(240, 0, 10, 10), None);
+        assert_eq!(config().clip_region(0, 280, 10, 10), None);
+    }
+
+    #[test]
+    fn clip_region_zero_size() {
+        assert_eq!(config().clip_region(10, 10, 0, 10), None);
+        assert_eq!(config().clip_region(10, 10, 10, 0), None);
+        assert_eq!(config().clip_region(0, 0, 0, 0), None);
+    }
+
+    #[test]
+    fn pixels_through_last_pixel() {
+        let config = config();
+        assert_eq!(config.pixels_through(0, 0), 1);
+        assert_eq!(config.pixels_through(239, 0), 240);
+        assert_eq!(config.pixels_through(239, 279), config.pixels());
+    }
+
+    #[test]
+    fn window_rows_merge_full_width_rows() {
+        use crate::{Rgb444Format, Rgb565Format};
+        use std::vec::Vec;
+
+        let config = config();
+        let rows: Vec<_> = config
+            .window_rows::<Rgb565Format>(10, 2, 19, 3)
+            .unwrap()
+            .collect();
+        assert_eq!(rows, [980..1000, 1460..1480]);
+        let rows: Vec<_> = config
+            .window_rows::<Rgb565Format>(0, 2, 239, 3)
+            .unwrap()
+            .collect();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0], 960..1920);
+        assert!(config.window_rows::<Rgb444Format>(0, 0, 1, 1).is_none());
+
+        let pixels: Vec<_> = config.window_pixels(10, 2, 11, 3).collect();
+        assert_eq!(pixels, [490, 491, 730, 731]);
+    }
+
+    #[test]
+    fn clip_region_landscape() {
+        let mut config = config();
+        config.orientation = Orientation::Landscape;
+        config.update_geometry();
+        assert_eq!(
+            config.clip_region(270, 200, 20, 100),
+            Some((270, 200, 279, 239))
+        );
+    }
+}
@@ -0,0 +1,272 @@
+use crate::Region;
+
+/// Set of up to `N` dirty regions for partial updates.
+///
+/// Added regions that overlap or share an edge with a stored region are merged into
+/// their bounding box, so no pixel is pushed twice. When all `N` slots are taken, the
+/// new region is merged with the stored region whose bounding box grows the least.
+/// Without any slots (`N = 0`) or after `mark_full_screen`, the whole display is
+/// pushed instead.
+///
+/// The regions are pushed with `show_regions`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DirtyRegions<const N: usize> {
+    regions: [Region; N],
+    len: usize,
+    full_screen: bool,
+}
+
+impl<const N: usize> DirtyRegions<N> {
+    /// Creates an empty set.
+    pub const fn new() -> Self {
+        DirtyRegions {
+            regions: [Region {
+                x: 0,
+                y: 0,
+                width: 0,
+                height: 0,
+            }; N],
+            len: 0,
+            full_screen: false,
+        }
+    }
+
+    /// Marks a region as dirty.
+    ///
+    /// Regions with a zero width or height are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `region` - The region that has changed.
+    pub fn add(&mut self, region: Region) {
+        if region.width == 0 || region.height == 0 || self.full_screen {
+            return;
+        }
+        if N == 0 {
+            self.full_screen = true;
+            return;
+        }
+
+        let mut region = region;
+        loop {
+            // Absorb every stored region the new one touches, the bounding box may in
+            // turn touch regions that were separate before
+            let mut i = 0;
+            while i < self.len {
+                if touches(&self.regions[i], &region) {
+                    region = bounding_box(&self.regions[i], &region);
+                    self.remove(i);
+                    i = 0;
+                } else {
+                    i += 1;
+                }
+            }
+
+            if self.len < N {
+                self.regions[self.len] = region;
+                self.len += 1;
+                return;
+            }
+
+            // No slot is left, grow the region that needs the least additional area
+            let mut best = 0;
+            let mut best_growth = u64::MAX;
+            for (i, stored) in self.regions.iter().enumerate() {
+                let growth = area(&bounding_box(stored, &region)) - area(stored);
+                if growth < best_growth {
+                    best = i;
+                    best_growth = growth;
+                }
+            }
+            region = bounding_box(&self.regions[best], &region);
+            self.remove(best);
+        }
+    }
+
+    /// Marks the whole display as dirty.
+    pub fn mark_full_screen(&mut self) {
+        self.full_screen = true;
+        self.len = 0;
+    }
+
+    /// Returns `true` if the whole display has to be pushed.
+    pub fn is_full_screen(&self) -> bool {
+        self.full_screen
+    }
+
+    /// Returns `true` if nothing has to be pushed.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0 && !self.full_screen
+    }
+
+    /// Returns the stored regions. Empty if the whole display is dirty.
+    pub fn regions(&self) -> &[Region] {
+        &self.regions[..self.len]
+    }
+
+    /// Removes all regions.
+    pub fn clear(&mut self) {
+        self.len = 0;
+        self.full_screen = false;
+    }
+
+    /// Removes the region at `index`, the order of the regions is not kept.
+    fn remove(&mut self, index: usize) {
+        self.len -= 1;
+        self.regions[index] = self.regions[self.len];
+    }
+}
+
+impl<const N: usize> Default for DirtyRegions<N> {
+    fn default() -> Self {
+        DirtyRegions::new()
+    }
+}
+
+/// Returns `true` if two regions overlap or share part of an edge.
+fn touches(a: &Region, b: &Region) -> bool {
+    let (ax0, ay0, ax1, ay1) = edges(a);
+    let (bx0, by0, bx1, by1) = edges(b);
+
+    let x_overlap = ax0 < bx1 && bx0 < ax1;
+    let y_overlap = ay0 < by1 && by0 < ay1;
+    let x_touch = ax0 <= bx1 && bx0 <= ax1;
+    let y_touch = ay0 <= by1 && by0 <= ay1;
+
+    // Regions touching only at a corner are kept apart
+    (x_overlap && y_touch) || (y_overlap && x_touch)
+}
+
+/// Returns the smallest region containing both regions.
+fn bounding_box(a: &Region, b: &Region) -> Region {
+    let (ax0, ay0, ax1, ay1) = edges(a);
+    let (bx0, by0, bx1, by1) = edges(b);
+    let (x, y) = (ax0.min(bx0), ay0.min(by0));
+
+    Region {
+        x: x as u16,
+        y: y as u16,
+        width: (ax1.max(bx1) - x).min(u32::MAX as u64) as u32,
+        height: (ay1.max(by1) - y).min(u32::MAX as u64) as u32,
+    }
+}
+
+/// Returns the left, top, right and bottom edge of a region (right and bottom exclusive).
+fn edges(region: &Region) -> (u64, u64, u64, u64) {
+    let x = region.x as u64;
+    let y = region.y as u64;
+    (x, y, x + region.width as u64, y + region.height as u64)
+}
+
+/// Returns the number of pixels of a region.
+fn area(region: &Region) -> u64 {
+    region.width as u64 * region.height as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn region(x: u16, y: u16, width: u32, height: u32) -> Region {
+        Region {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    #[test]
+    fn separate_regions_are_kept() {
+        let mut dirty = DirtyRegions::<4>::new();
+        dirty.add(region(0, 0, 10, 10));
+        dirty.add(region(20, 20, 10, 10));
+        assert_eq!(
+            dirty.regions(),
+            &[region(0, 0, 10, 10), region(20, 20, 10, 10)]
+        );
+    }
+
+    #[test]
+    fn overlapping_regions_are_merged() {
+        let mut dirty = DirtyRegions::<4>::new();
+        dirty.add(region(0, 0, 10, 10));
+        dirty.add(region(5, 5, 10, 10));
+        assert_eq!(dirty.regions(), &[region(0, 0, 15, 15)]);
+    }
+
+    #[test]
+    fn adjacent_regions_are_merged() {
+        let mut dirty = DirtyRegions::<4>::new();
+        dirty.add(region(0, 0, 10, 10));
+        dirty.add(region(10, 0, 10, 10));
+        dirty.add(region(0, 10, 20, 5));
+        assert_eq!(dirty.regions(), &[region(0, 0, 20, 15)]);
+    }
+
+    #[test]
+    fn corner_contact_is_not_merged() {
+        let mut dirty = DirtyRegions::<4>::new();
+        dirty.add(region(0, 0, 10, 10));
+        dirty.add(region(10, 10, 10, 10));
+        assert_eq!(dirty.regions().len(), 2);
+    }
+
+    #[test]
+    fn contained_region_is_absorbed() {
+        let mut dirty = DirtyRegions::<4>::new();
+        dirty.add(region(0, 0, 100, 100));
+        dirty.add(region(10, 10, 5, 5));
+        assert_eq!(dirty.regions(), &[region(0, 0, 100, 100)]);
+    }
+
+    #[test]
+    fn merge_cascades_over_bridged_regions() {
+        let mut dirty = DirtyRegions::<4>::new();
+        dirty.add(region(0, 0, 10, 10));
+        dirty.add(region(30, 0, 10, 10));
+        dirty.add(region(5, 0, 30, 10));
+        assert_eq!(dirty.regions(), &[region(0, 0, 40, 10)]);
+    }
+
+    #[test]
+    fn zero_size_regions_are_ignored() {
+        let mut dirty = DirtyRegions::<4>::new();
+        dirty.add(region(0, 0, 0, 10));
+        dirty.add(region(0, 0, 10, 0));
+        assert!(dirty.is_empty());
+    }
+
+    #[test]
+    fn full_set_merges_with_the_closest_region() {
+        let mut dirty = DirtyRegions::<2>::new();
+        dirty.add(region(0, 0, 10, 10));
+        dirty.add(region(100, 100, 10, 10));
+        dirty.add(region(0, 20, 10, 10));
+        assert_eq!(
+            dirty.regions(),
+            &[region(100, 100, 10, 10), region(0, 0, 10, 30)]
+        );
+    }
+
+    #[test]
+    fn no_slots_fall_back_to_full_screen() {
+        let mut dirty = DirtyRegions::<0>::new();
+        dirty.add(region(0, 0, 10, 10));
+        assert!(dirty.is_full_screen());
+        assert!(!dirty.is_empty());
+    }
+
+    #[test]
+    fn full_screen_ignores_further_regions() {
+        let mut dirty = DirtyRegions::<4>::new();
+        dirty.add(region(0, 0, 10, 10));
+        dirty.mark_full_screen();
+        dirty.add(region(20, 20, 10, 10));
+        assert!(dirty.regions().is_empty());
+
+        dirty.clear();
+        assert!(dirty.is_empty());
+        assert!(!dirty.is_full_screen());
+    }
+}
@@ -0,0 +1,33 @@
+/// Errors returned by the ST7789V2 driver.
+///
+/// `SpiE` is the error type of the SPI bus (`DisplayError` for a
+/// [`DataCommandInterface`](crate::DataCommandInterface)) and `PinE` the error type
+/// shared by the control pins, so the underlying `embedded_hal` error is preserved and
+/// a wiring fault can be told apart from a bad coordinate or an undersized buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error<SpiE, PinE> {
+    /// Writing to the SPI bus or display interface failed.
+    Spi(SpiE),
+    /// Setting the data/command pin failed.
+    Dc(PinE),
+    /// Setting the chip select pin failed.
+    Cs(PinE),
+    /// Setting the reset pin failed.
+    Rst(PinE),
+    /// Reading the tearing effect pin failed.
+    Te(PinE),
+    /// Setting the backlight pin failed.
+    Backlight(PinE),
+    /// Setting the backlight PWM duty cycle failed.
+    Pwm(embedded_hal::pwm::ErrorKind),
+    /// The display is in sleep mode and has to be woken up before drawing.
+    Sleeping,
+    /// A coordinate or region lies outside of the display.
+    OutOfBounds,
+    /// The supplied buffer is smaller than the area it is supposed to cover.
+    BufferTooSmall,
+    /// The operation is not supported in the current orientation or mirroring.
+    Unsupported,
+    /// The panel configuration does not fit into the frame memory of the controller.
+    InvalidGeometry,
+}
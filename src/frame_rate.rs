@@ -0,0 +1,174 @@
+use embedded_hal::digital::OutputPin;
+
+use crate::panel::RAM_HEIGHT;
+use crate::{Backlight, Error, Instruction, Interface, PixelFormat, ST7789V2};
+
+/// Lowest frame rate the controller supports in Hz.
+const MIN_HZ: u8 = 39;
+/// Highest frame rate the controller supports in Hz.
+const MAX_HZ: u8 = 119;
+/// Largest back or front porch in lines.
+const MAX_PORCH: u8 = 0x7F;
+
+/// Frame rate and porch settings in normal mode.
+///
+/// The controller refreshes at `10 MHz / ((320 + front + back porch) * (250 + RTNA * 16))`.
+/// The line period RTNA is chosen to get as close to `hz` as possible for the given
+/// porches. Shorter porches leave more time for the line period and vice versa.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameRate {
+    /// Target frame rate in Hz, clamped to 39-119.
+    pub hz: u8,
+    /// Blanking lines before the first row, clamped to 1-127.
+    pub back_porch: u8,
+    /// Blanking lines after the last row, clamped to 1-127.
+    pub front_porch: u8,
+    /// Use column instead of dot inversion, which needs less power on some panels.
+    pub column_inversion: bool,
+}
+
+impl FrameRate {
+    /// 60 Hz with the default porches.
+    pub const HZ_60: FrameRate = FrameRate::new(60);
+
+    /// Creates a frame rate with the default porches of 12 lines and dot inversion.
+    ///
+    /// # Arguments
+    ///
+    /// * `hz` - Target frame rate in Hz (39 to 119).
+    pub const fn new(hz: u8) -> Self {
+        FrameRate {
+            hz,
+            back_porch: 0x0C,
+            front_porch: 0x0C,
+            column_inversion: false,
+        }
+    }
+
+    /// Returns a copy with the given back and front porch.
+    pub const fn with_porch(mut self, back_porch: u8, front_porch: u8) -> Self {
+        self.back_porch = back_porch;
+        self.front_porch = front_porch;
+        self
+    }
+
+    /// Returns a copy with column or dot inversion.
+    pub const fn with_column_inversion(mut self, column_inversion: bool) -> Self {
+        self.column_inversion = column_inversion;
+        self
+    }
+
+    /// Returns the PORCTRL parameters.
+    pub(crate) fn porch(&self) -> [u8; 5] {
+        // Idle and partial mode keep the reset defaults
+        [
+            self.back_porch.clamp(1, MAX_PORCH),
+            self.front_porch.clamp(1, MAX_PORCH),
+            0x00,
+            0x33,
+            0x33,
+        ]
+    }
+
+    /// Returns the FRCTRL2 parameter.
+    pub(crate) fn frctrl2(&self) -> u8 {
+        let [back, front, ..] = self.porch();
+        let lines = RAM_HEIGHT as u32 + back as u32 + front as u32;
+        let hz = self.hz.clamp(MIN_HZ, MAX_HZ) as u32;
+
+        // Solve the frame rate formula for RTNA and round to the nearest setting
+        let clocks = 10_000_000 / (hz * lines);
+        let rtna = (clocks.saturating_sub(250) + 8) / 16;
+        let nla = if self.column_inversion { 0x07 } else { 0x00 };
+
+        (nla << 5) | rtna.min(0x1F) as u8
+    }
+}
+
+impl Default for FrameRate {
+    fn default() -> Self {
+        FrameRate::HZ_60
+    }
+}
+
+impl<DI, RST, PF, TE, BL> ST7789V2<DI, RST, PF, TE, BL>
+where
+    DI: Interface,
+    RST: OutputPin<Error = DI::PinError>,
+    BL: Backlight<DI::PinError>,
+    PF: PixelFormat,
+{
+    /// Sets the frame rate and porches in normal mode.
+    ///
+    /// Lower rates save power, higher rates give smoother animation. The setting is kept
+    /// for later calls to `init` and replaces the frame rate of the init sequence.
+    ///
+    /// # Arguments
+    ///
+    /// * `frame_rate` - The new frame rate and porch settings.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn set_frame_rate(
+        &mut self,
+        frame_rate: FrameRate,
+    ) -> Result<(), Error<DI::SpiError, DI::PinError>> {
+        self.write_command(Instruction::PorCtrl as u8, &frame_rate.porch())?;
+        self.write_command(Instruction::FrCtrl2 as u8, &[frame_rate.frctrl2()])?;
+        self.config.frame_rate = Some(frame_rate);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{display, MockDelay};
+    use crate::{GammaCurve, PanelConfig};
+
+    #[test]
+    fn frctrl2_matches_the_datasheet_table() {
+        // Frame rates listed for RTNA with the default porches
+        for (hz, frctrl2) in [(119, 0x00), (99, 0x03), (60, 0x0F), (53, 0x13), (39, 0x1F)] {
+            assert_eq!(FrameRate::new(hz).frctrl2(), frctrl2, "{hz} Hz");
+        }
+        assert_eq!(
+            FrameRate::new(60).with_column_inversion(true).frctrl2(),
+            0xEF
+        );
+    }
+
+    #[test]
+    fn frame_rate_is_clamped() {
+        assert_eq!(FrameRate::new(0).frctrl2(), 0x1F);
+        assert_eq!(FrameRate::new(255).frctrl2(), 0x00);
+        // Long porches leave no time for the line period at high rates
+        assert_eq!(FrameRate::new(119).with_porch(127, 127).frctrl2(), 0x00);
+    }
+
+    #[test]
+    fn porches_are_clamped() {
+        assert_eq!(FrameRate::HZ_60.porch(), [0x0C, 0x0C, 0x00, 0x33, 0x33]);
+        let frame_rate = FrameRate::new(60).with_porch(0, 255);
+        assert_eq!(frame_rate.porch(), [0x01, 0x7F, 0x00, 0x33, 0x33]);
+        // 320 + 1 + 127 lines
+        assert_eq!(frame_rate.frctrl2(), 0x08);
+    }
+
+    #[test]
+    fn init_restores_frame_rate_and_gamma() {
+        let mut display = display(PanelConfig::PANEL_240X280);
+        display.set_frame_rate(FrameRate::new(99)).unwrap();
+        display.set_gamma_curve(GammaCurve::Gamma3).unwrap();
+        display.interface.commands.clear();
+
+        display.init(&mut MockDelay).unwrap();
+        assert_eq!(display.interface.last(0xC6), Some(&[0x03][..]));
+        assert_eq!(
+            display.interface.last(0xB2),
+            Some(&[0x0C, 0x0C, 0x00, 0x33, 0x33][..])
+        );
+        assert_eq!(display.interface.last(0x26), Some(&[0x04][..]));
+    }
+}
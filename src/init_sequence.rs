@@ -0,0 +1,176 @@
+/// A single command of an [`InitSequence`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InitStep {
+    /// Command byte.
+    pub command: u8,
+    /// Parameters sent after the command byte.
+    pub params: &'static [u8],
+    /// Delay in milliseconds after the command has been sent.
+    pub delay_ms: u32,
+}
+
+impl InitStep {
+    /// Creates a step without a trailing delay.
+    ///
+    /// # Arguments
+    ///
+    /// * `command` - Command byte.
+    /// * `params` - Parameters sent after the command byte.
+    pub const fn new(command: u8, params: &'static [u8]) -> Self {
+        InitStep {
+            command,
+            params,
+            delay_ms: 0,
+        }
+    }
+
+    /// Returns a copy that waits `delay_ms` milliseconds after the command.
+    pub const fn with_delay(mut self, delay_ms: u32) -> Self {
+        self.delay_ms = delay_ms;
+        self
+    }
+}
+
+/// Vendor specific tuning commands sent by `init`.
+///
+/// Panel vendors ship different porch, power, VCOM and gamma settings. The sequence
+/// only covers those tuning commands: `init` still performs the hardware reset and
+/// sends MADCTL, COLMOD, inversion, sleep out and display on according to the driver
+/// configuration, with the sequence running in between.
+///
+/// ```ignore
+/// const MY_PANEL: InitSequence = InitSequence::new(&[
+///     InitStep::new(0xB2, &[0x0C, 0x0C, 0x00, 0x33, 0x33]),
+///     InitStep::new(0xBB, &[0x2B]),
+/// ]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InitSequence {
+    steps: &'static [InitStep],
+}
+
+impl InitSequence {
+    /// Tuning table of the Waveshare 1.69" 240x280 module.
+    pub const WAVESHARE_1_69: InitSequence = InitSequence::new(&[
+        InitStep::new(0xB2, &[0x0B, 0x0B, 0x00, 0x33, 0x35]),
+        InitStep::new(0xB7, &[0x11]),
+        InitStep::new(0xBB, &[0x35]),
+        InitStep::new(0xC0, &[0x2C]),
+        InitStep::new(0xC2, &[0x01]),
+        InitStep::new(0xC3, &[0x0D]),
+        InitStep::new(0xC4, &[0x20]),
+        InitStep::new(0xC6, &[0x13]),
+        InitStep::new(0xD0, &[0xA4, 0xA1]),
+        InitStep::new(0xD6, &[0xA1]),
+        InitStep::new(
+            0xE0,
+            &[
+                0xF0, 0x06, 0x0B, 0x0A, 0x09, 0x26, 0x29, 0x33, 0x41, 0x18, 0x16, 0x15, 0x29, 0x2D,
+            ],
+        ),
+        InitStep::new(
+            0xE1,
+            &[
+                0xF0, 0x04, 0x08, 0x08, 0x07, 0x03, 0x28, 0x32, 0x40, 0x3B, 0x19, 0x18, 0x2A, 0x2E,
+            ],
+        ),
+        InitStep::new(0xE4, &[0x25, 0x00, 0x00]),
+    ]);
+
+    /// Tuning table of the Waveshare 1.3" and 1.54" 240x240 modules.
+    pub const WAVESHARE_1_3: InitSequence = InitSequence::new(&[
+        InitStep::new(0xB2, &[0x0C, 0x0C, 0x00, 0x33, 0x33]),
+        InitStep::new(0xB7, &[0x35]),
+        InitStep::new(0xBB, &[0x19]),
+        InitStep::new(0xC0, &[0x2C]),
+        InitStep::new(0xC2, &[0x01]),
+        InitStep::new(0xC3, &[0x12]),
+        InitStep::new(0xC4, &[0x20]),
+        InitStep::new(0xC6, &[0x0F]),
+        InitStep::new(0xD0, &[0xA4, 0xA1]),
+        InitStep::new(
+            0xE0,
+            &[
+                0xD0, 0x04, 0x0D, 0x11, 0x13, 0x2B, 0x3F, 0x54, 0x4C, 0x18, 0x0D, 0x0B, 0x1F, 0x23,
+            ],
+        ),
+        InitStep::new(
+            0xE1,
+            &[
+                0xD0, 0x04, 0x0C, 0x11, 0x13, 0x2C, 0x3F, 0x44, 0x51, 0x2F, 0x1F, 0x1F, 0x20, 0x23,
+            ],
+        ),
+    ]);
+
+    /// Tuning table of the Waveshare 1.47" 172x320 module.
+    pub const WAVESHARE_1_47: InitSequence = InitSequence::new(&[
+        InitStep::new(0xB2, &[0x0C, 0x0C, 0x00, 0x33, 0x33]),
+        InitStep::new(0xB7, &[0x35]),
+        InitStep::new(0xBB, &[0x35]),
+        InitStep::new(0xC0, &[0x2C]),
+        InitStep::new(0xC2, &[0x01]),
+        InitStep::new(0xC3, &[0x13]),
+        InitStep::new(0xC4, &[0x20]),
+        InitStep::new(0xC6, &[0x0F]),
+        InitStep::new(0xD0, &[0xA4, 0xA1]),
+        InitStep::new(0xD6, &[0xA1]),
+        InitStep::new(
+            0xE0,
+            &[
+                0xF0, 0x00, 0x04, 0x04, 0x04, 0x05, 0x29, 0x33, 0x3E, 0x38, 0x12, 0x12, 0x28, 0x30,
+            ],
+        ),
+        InitStep::new(
+            0xE1,
+            &[
+                0xF0, 0x07, 0x0A, 0x0D, 0x0B, 0x07, 0x28, 0x33, 0x3E, 0x36, 0x14, 0x14, 0x29, 0x32,
+            ],
+        ),
+    ]);
+
+    /// Tuning table of the Waveshare 2.0" 240x320 module.
+    pub const WAVESHARE_2_0: InitSequence = InitSequence::new(&[
+        InitStep::new(0xB2, &[0x0C, 0x0C, 0x00, 0x33, 0x33]),
+        InitStep::new(0xB7, &[0x35]),
+        InitStep::new(0xBB, &[0x1F]),
+        InitStep::new(0xC0, &[0x2C]),
+        InitStep::new(0xC2, &[0x01]),
+        InitStep::new(0xC3, &[0x12]),
+        InitStep::new(0xC4, &[0x20]),
+        InitStep::new(0xC6, &[0x0F]),
+        InitStep::new(0xD0, &[0xA4, 0xA1]),
+        InitStep::new(
+            0xE0,
+            &[
+                0xD0, 0x08, 0x11, 0x08, 0x0C, 0x15, 0x39, 0x33, 0x50, 0x36, 0x13, 0x14, 0x29, 0x2D,
+            ],
+        ),
+        InitStep::new(
+            0xE1,
+            &[
+                0xD0, 0x08, 0x10, 0x08, 0x06, 0x06, 0x39, 0x44, 0x51, 0x0B, 0x16, 0x14, 0x2F, 0x31,
+            ],
+        ),
+    ]);
+
+    /// No tuning at all, the controller keeps its reset defaults.
+    ///
+    /// This is what most generic breakout boards are shipped with.
+    pub const RESET_DEFAULTS: InitSequence = InitSequence::new(&[]);
+
+    /// Creates a sequence from a list of steps.
+    pub const fn new(steps: &'static [InitStep]) -> Self {
+        InitSequence { steps }
+    }
+
+    /// Returns the steps of the sequence.
+    pub fn steps(&self) -> &'static [InitStep] {
+        self.steps
+    }
+}
+
+impl Default for InitSequence {
+    fn default() -> Self {
+        InitSequence::WAVESHARE_1_69
+    }
+}
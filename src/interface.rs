@@ -0,0 +1,303 @@
+use core::convert::Infallible;
+use core::marker::PhantomData;
+
+use display_interface::{DataFormat, DisplayError, WriteOnlyDataCommand};
+use embedded_hal::digital::OutputPin;
+use embedded_hal::spi::{Operation, SpiBus, SpiDevice};
+
+use crate::Error;
+
+/// Interface the display is connected to.
+///
+/// Implemented for [`SpiInterface`] over an `SpiBus`, for [`SpiDeviceInterface`]
+/// over an `SpiDevice` and for [`DataCommandInterface`] over any `display-interface`
+/// backend.
+pub trait Interface {
+    /// Error type of the SPI bus or device.
+    type SpiError;
+    /// Error type of the control pins.
+    type PinError;
+
+    /// Sends a command byte followed by its parameters.
+    ///
+    /// # Arguments
+    ///
+    /// * `command` - Command to write.
+    /// * `params` - Parameters for the command.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    fn write_command(
+        &mut self,
+        command: u8,
+        params: &[u8],
+    ) -> Result<(), Error<Self::SpiError, Self::PinError>>;
+
+    /// Sends data following the last command, e.g. pixels after RAMWR.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - Data to write.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    fn write_data(&mut self, data: &[u8]) -> Result<(), Error<Self::SpiError, Self::PinError>>;
+}
+
+/// Interface that can read data back from the controller.
+///
+/// Implemented for [`SpiInterface`] and [`SpiDeviceInterface`]. The data is read either
+/// over a separate MISO (SDO) line or over the bidirectional SDA line, if the HAL turns
+/// the line around for `read`. The controller only supports reads at a lower clock
+/// than writes (about 6 MHz), so the bus may have to be slowed down.
+pub trait ReadInterface: Interface {
+    /// Sends a command byte and reads the returned data.
+    ///
+    /// # Arguments
+    ///
+    /// * `command` - Command to write.
+    /// * `buffer` - Buffer the returned data is read into.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    fn read_command(
+        &mut self,
+        command: u8,
+        buffer: &mut [u8],
+    ) -> Result<(), Error<Self::SpiError, Self::PinError>>;
+}
+
+/// Placeholder for an optional pin that is not connected.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct NoPin;
+
+/// SPI bus interface with a chip select pin driven by the driver.
+///
+/// The driver owns the bus and toggles `CS` itself around every transfer.
+pub struct SpiInterface<SPI, DC, CS> {
+    /// SPI bus.
+    pub(crate) spi: SPI,
+    /// Data/command pin.
+    pub(crate) dc: DC,
+    /// Chip select pin.
+    pub(crate) cs: CS,
+}
+
+impl<SPI, DC, CS> SpiInterface<SPI, DC, CS> {
+    /// Creates a new SPI bus interface.
+    ///
+    /// # Arguments
+    ///
+    /// * `spi` - SPI bus.
+    /// * `dc` - Data/command pin.
+    /// * `cs` - Chip select pin.
+    pub fn new(spi: SPI, dc: DC, cs: CS) -> Self {
+        SpiInterface { spi, dc, cs }
+    }
+
+    /// Releases the SPI bus and the pins.
+    pub fn release(self) -> (SPI, DC, CS) {
+        (self.spi, self.dc, self.cs)
+    }
+}
+
+impl<SPI, DC, CS, PinE> Interface for SpiInterface<SPI, DC, CS>
+where
+    SPI: SpiBus<u8>,
+    DC: OutputPin<Error = PinE>,
+    CS: OutputPin<Error = PinE>,
+{
+    type SpiError = SPI::Error;
+    type PinError = PinE;
+
+    fn write_command(&mut self, command: u8, params: &[u8]) -> Result<(), Error<SPI::Error, PinE>> {
+        self.dc.set_low().map_err(Error::Dc)?;
+        self.cs.set_low().map_err(Error::Cs)?;
+        self.spi.write(&[command]).map_err(Error::Spi)?;
+        if !params.is_empty() {
+            // DC must not change before the command byte has left the bus
+            self.spi.flush().map_err(Error::Spi)?;
+            self.dc.set_high().map_err(Error::Dc)?;
+            self.spi.write(params).map_err(Error::Spi)?;
+        }
+        self.spi.flush().map_err(Error::Spi)?;
+        self.cs.set_high().map_err(Error::Cs)?;
+        Ok(())
+    }
+
+    fn write_data(&mut self, data: &[u8]) -> Result<(), Error<SPI::Error, PinE>> {
+        self.dc.set_high().map_err(Error::Dc)?;
+        self.cs.set_low().map_err(Error::Cs)?;
+        self.spi.write(data).map_err(Error::Spi)?;
+        self.spi.flush().map_err(Error::Spi)?;
+        self.cs.set_high().map_err(Error::Cs)?;
+        Ok(())
+    }
+}
+
+impl<SPI, DC, CS, PinE> ReadInterface for SpiInterface<SPI, DC, CS>
+where
+    SPI: SpiBus<u8>,
+    DC: OutputPin<Error = PinE>,
+    CS: OutputPin<Error = PinE>,
+{
+    fn read_command(
+        &mut self,
+        command: u8,
+        buffer: &mut [u8],
+    ) -> Result<(), Error<SPI::Error, PinE>> {
+        // Chip select has to stay active between the command and the returned data
+        self.dc.set_low().map_err(Error::Dc)?;
+        self.cs.set_low().map_err(Error::Cs)?;
+        self.spi.write(&[command]).map_err(Error::Spi)?;
+        self.spi.flush().map_err(Error::Spi)?;
+        self.spi.read(buffer).map_err(Error::Spi)?;
+        self.cs.set_high().map_err(Error::Cs)?;
+        Ok(())
+    }
+}
+
+/// SPI device interface for buses shared with other devices.
+///
+/// Chip select is managed by the bus manager (e.g. `embedded-hal-bus`). An `SpiDevice`
+/// transaction cannot toggle the data/command pin, so the command byte and its
+/// parameters are sent as two separate transactions and chip select is released in
+/// between. Another device may use the bus at that point. The controller keeps the last
+/// command while its chip select is inactive and only samples the data/command pin
+/// while it is active, so the parameters still reach the right command. Every
+/// `write_data` call is a single transaction, so the pixels of a frame pushed by `show`
+/// are never interleaved with other traffic.
+pub struct SpiDeviceInterface<SPI, DC> {
+    /// SPI device.
+    pub(crate) spi: SPI,
+    /// Data/command pin.
+    pub(crate) dc: DC,
+}
+
+impl<SPI, DC> SpiDeviceInterface<SPI, DC> {
+    /// Creates a new SPI device interface.
+    ///
+    /// # Arguments
+    ///
+    /// * `spi` - SPI device.
+    /// * `dc` - Data/command pin.
+    pub fn new(spi: SPI, dc: DC) -> Self {
+        SpiDeviceInterface { spi, dc }
+    }
+
+    /// Releases the SPI device and the data/command pin.
+    pub fn release(self) -> (SPI, DC) {
+        (self.spi, self.dc)
+    }
+}
+
+impl<SPI, DC> Interface for SpiDeviceInterface<SPI, DC>
+where
+    SPI: SpiDevice<u8>,
+    DC: OutputPin,
+{
+    type SpiError = SPI::Error;
+    type PinError = DC::Error;
+
+    fn write_command(
+        &mut self,
+        command: u8,
+        params: &[u8],
+    ) -> Result<(), Error<SPI::Error, DC::Error>> {
+        // DC cannot change within a transaction, chip select is released before the
+        // parameters
+        self.dc.set_low().map_err(Error::Dc)?;
+        self.spi.write(&[command]).map_err(Error::Spi)?;
+        if !params.is_empty() {
+            self.dc.set_high().map_err(Error::Dc)?;
+            self.spi.write(params).map_err(Error::Spi)?;
+        }
+        Ok(())
+    }
+
+    fn write_data(&mut self, data: &[u8]) -> Result<(), Error<SPI::Error, DC::Error>> {
+        self.dc.set_high().map_err(Error::Dc)?;
+        self.spi.write(data).map_err(Error::Spi)
+    }
+}
+
+impl<SPI, DC> ReadInterface for SpiDeviceInterface<SPI, DC>
+where
+    SPI: SpiDevice<u8>,
+    DC: OutputPin,
+{
+    fn read_command(
+        &mut self,
+        command: u8,
+        buffer: &mut [u8],
+    ) -> Result<(), Error<SPI::Error, DC::Error>> {
+        // The data/command pin is not sampled while reading, so the command and the
+        // returned data fit into one transaction
+        self.dc.set_low().map_err(Error::Dc)?;
+        self.spi
+            .transaction(&mut [Operation::Write(&[command]), Operation::Read(buffer)])
+            .map_err(Error::Spi)
+    }
+}
+
+/// Interface over a `display-interface` backend.
+///
+/// Wraps any `WriteOnlyDataCommand`, e.g. an 8-bit 8080 parallel GPIO or a PIO driven
+/// interface, so the driver can run on it. Errors of the backend are reported as
+/// `Error::Spi(DisplayError)`. `PinE` is the error type of the reset pin and is usually
+/// inferred.
+pub struct DataCommandInterface<DI, PinE = Infallible> {
+    /// `display-interface` backend.
+    pub(crate) di: DI,
+    _pin_error: PhantomData<PinE>,
+}
+
+impl<DI, PinE> DataCommandInterface<DI, PinE> {
+    /// Creates a new interface over a `display-interface` backend.
+    ///
+    /// # Arguments
+    ///
+    /// * `di` - `display-interface` backend.
+    pub fn new(di: DI) -> Self {
+        DataCommandInterface {
+            di,
+            _pin_error: PhantomData,
+        }
+    }
+
+    /// Releases the `display-interface` backend.
+    pub fn release(self) -> DI {
+        self.di
+    }
+}
+
+impl<DI, PinE> Interface for DataCommandInterface<DI, PinE>
+where
+    DI: WriteOnlyDataCommand,
+{
+    type SpiError = DisplayError;
+    type PinError = PinE;
+
+    fn write_command(
+        &mut self,
+        command: u8,
+        params: &[u8],
+    ) -> Result<(), Error<DisplayError, PinE>> {
+        self.di
+            .send_commands(DataFormat::U8(&[command]))
+            .map_err(Error::Spi)?;
+        if !params.is_empty() {
+            self.di
+                .send_data(DataFormat::U8(params))
+                .map_err(Error::Spi)?;
+        }
+        Ok(())
+    }
+
+    fn write_data(&mut self, data: &[u8]) -> Result<(), Error<DisplayError, PinE>> {
+        self.di.send_data(DataFormat::U8(data)).map_err(Error::Spi)
+    }
+}
@@ -1,14 +1,61 @@
 #![no_std]
-#![no_main]
 
-use embedded_graphics::pixelcolor::raw::RawU16;
-use embedded_graphics::{pixelcolor::Rgb565, prelude::*};
+#[cfg(test)]
+extern crate std;
+
+#[cfg(feature = "async")]
+mod asynch;
+mod backlight;
+mod brightness;
+mod builder;
+mod color;
+mod config;
+mod dirty;
+mod error;
+mod frame_rate;
+mod init_sequence;
+mod interface;
+#[cfg(test)]
+mod mock;
+mod orientation;
+mod panel;
+mod pixel_format;
+mod power;
+mod read;
+mod scroll;
+mod tearing;
+
+#[cfg(feature = "async")]
+pub use asynch::{AsyncInterface, ST7789V2Async};
+
+pub use backlight::{Backlight, BacklightPin, BacklightPwm};
+pub use brightness::CabcMode;
+pub use builder::ST7789V2Builder;
+pub use color::GammaCurve;
+pub use dirty::DirtyRegions;
+pub use error::Error;
+pub use frame_rate::FrameRate;
+pub use init_sequence::{InitSequence, InitStep};
+pub use interface::{
+    DataCommandInterface, Interface, NoPin, ReadInterface, SpiDeviceInterface, SpiInterface,
+};
+pub use orientation::{Mirror, Orientation};
+pub use panel::{ColorOrder, PanelConfig};
+pub use pixel_format::{PixelFormat, Rgb444Format, Rgb565Format, Rgb666Format, Rgb888Format};
+pub use power::PowerState;
+pub use read::{DisplayId, DisplayStatus, Madctl, PowerMode};
+pub use tearing::TearingEffect;
+
+use config::DisplayConfig;
+use pixel_format::{fill_chunk, pack_chunk, pixel_capacity, CHUNK_SIZE, RUN_SIZE};
+
+use core::convert::Infallible;
+use core::marker::PhantomData;
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rectangle;
 use embedded_hal::delay::DelayNs;
 use embedded_hal::digital::OutputPin;
-use embedded_hal::spi::SpiBus;
 
-pub const HORIZONTAL: u16 = 0;
-pub const VERTICAL: u16 = 1;
 /// Enumeration of instructions for the ST7789V2 display.
 pub enum Instruction {
     Nop = 0x00, // No Operation
@@ -23,6 +70,12 @@ pub enum Instruction {
     RddSt = 0x09, // Read Display Status
     // Description: Reads the current status of the display, such as inversion, sleep, and display modes.
     // Use: Used to check the active state of the display, including inversion and partial modes.
+    RdDPm = 0x0A, // Read Display Power Mode (RDDPM)
+    // Description: Returns the booster, idle, partial, sleep, normal and display on state.
+    // Use: Used to verify the power state of the display after initialization.
+    RdDMadCtl = 0x0B, // Read Display MADCTL (RDDMADCTL)
+    // Description: Returns the current memory access control settings.
+    // Use: Used to verify the orientation and color order written with MADCTL.
     SlpIn = 0x10, // Enter Sleep Mode (SLPIN)
     // Description: Puts the display into sleep mode, reducing power consumption but retaining memory contents.
     // Use: Requires 5ms delay before issuing new commands after sleep is activated.
@@ -84,6 +137,15 @@ pub enum Instruction {
     /// - MH (Bit 2): Horizontal refresh order (0 = left-to-right, 1 = right-to-left)
     // Description: Controls the orientation of the display (rotation, mirroring) and color order (RGB/BGR).
     // Use: Configures the display's orientation and pixel arrangement for different viewing angles.
+    VScSAd = 0x37, // Vertical Scroll Start Address of RAM (VSCSAD)
+    // Description: Sets the frame memory line shown at the top of the vertical scrolling area.
+    // Use: Scrolls the content of the scrolling area defined by VSCRDEF without rewriting it.
+    IdmOff = 0x38, // Idle Mode Off (IDMOFF)
+    // Description: Leaves idle mode and shows the full color depth again.
+    // Use: Restores normal colors after low-power idle mode.
+    IdmOn = 0x39, // Idle Mode On (IDMON)
+    // Description: Enters idle mode, where only 8 colors are shown using the MSB of each channel.
+    // Use: Reduces power consumption when the display only shows simple content.
     ColMod = 0x3A, // Pixel Format Set (COLMOD)
     /// 3 bits: D6, D5, D4.
     /// - D6: RGB interface color format (101 = 65K colors, 110 = 262K colors)
@@ -152,6 +214,14 @@ pub enum Instruction {
     // No bit-level details, reads diagnostic results from the brightness control system.
     // Description: Returns diagnostic results for automatic brightness control functionality.
     // Use: Useful for debugging and verifying the health of the brightness control circuitry.
+    PorCtrl = 0xB2, // Porch Setting (PORCTRL)
+    // Description: Sets the back and front porch in normal, idle and partial mode.
+    // Use: Adjusts the blanking lines, which affects the frame rate.
+    FrCtrl2 = 0xC6, // Frame Rate Control in Normal Mode (FRCTRL2)
+    // - NLA[2:0] (bits 7-5): Inversion selection (0 = dot, 7 = column inversion)
+    // - RTNA[4:0] (bits 4-0): Line period, selects the frame rate from 119 Hz to 39 Hz
+    // Description: Sets the frame rate and inversion in normal mode.
+    // Use: Lowers the refresh for power savings or raises it for smoother animation.
     RdId1 = 0xDA, // Read ID1 (RDID1)
     // No bit-level details, reads the first 8 bits of the display identification.
     // Description: Reads the first part of the display identification (Manufacturer ID).
@@ -161,13 +231,19 @@ pub enum Instruction {
     // Description: Reads the second part of the display identification (Module/Driver ID).
     // Use: Useful for identifying the specific module version.
     RdId3 = 0xDC, // Read ID3 (RDID3)
-                  // No bit-level details, reads the third 8 bits of the display identification.
-                  // Description: Reads the third part of the display identification (Additional ID data).
-                  // Use: Provides additional identification details about the display hardware.
+    // No bit-level details, reads the third 8 bits of the display identification.
+    // Description: Reads the third part of the display identification (Additional ID data).
+    // Use: Provides additional identification details about the display hardware.
+    PvGamCtrl = 0xE0, // Positive Voltage Gamma Control (PVGAMCTRL)
+    // Description: Sets the 14 gamma correction values for the positive source voltage.
+    // Use: Fine-tunes the gamma curve of a specific panel.
+    NvGamCtrl = 0xE1, // Negative Voltage Gamma Control (NVGAMCTRL)
+                      // Description: Sets the 14 gamma correction values for the negative source voltage.
+                      // Use: Fine-tunes the gamma curve of a specific panel together with PVGAMCTRL.
 }
 
 /// Structure to represent a region.
-#[derive(Copy, Clone, Default, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
 pub struct Region {
     pub x: u16,
     pub y: u16,
@@ -176,81 +252,128 @@ pub struct Region {
 }
 
 /// Driver for the ST7789V2 display.
-pub struct ST7789V2<SPI, DC, CS, RST>
-where
-    SPI: SpiBus<u8>,
-    DC: OutputPin,
-    CS: OutputPin,
-    RST: OutputPin,
-{
-    /// SPI interface.
-    spi: SPI,
-
-    /// Data/command pin.
-    dc: DC,
-
-    /// Chip select pin.
-    cs: CS,
+///
+/// Use [`ST7789V2Builder`] to create an instance. `PF` is the [`PixelFormat`] used on
+/// the interface and defaults to RGB565. `TE` is the optional tearing effect input pin.
+pub struct ST7789V2<DI, RST, PF = Rgb565Format, TE = NoPin, BL = NoPin> {
+    /// Interface the display is connected to.
+    interface: DI,
 
     /// Reset pin.
     rst: RST,
 
-    /// Whether the display is RGB (true) or BGR (false).
-    _rgb: bool,
-    /// Screen Direction Horizontal or vertical
-    sd: u16,
+    /// Tearing effect input pin.
+    te: TE,
 
-    /// Global image offset.
-    width: u32,
-    height: u32,
-    regions: [Option<Region>; 10],
+    /// Backlight of the module.
+    backlight: BL,
+
+    /// Panel configuration, orientation and derived geometry.
+    config: DisplayConfig,
+    _format: PhantomData<PF>,
 }
 
-impl<SPI, DC, CS, RST> ST7789V2<SPI, DC, CS, RST>
+impl<DI, RST, PF, TE, BL> ST7789V2<DI, RST, PF, TE, BL>
 where
-    SPI: SpiBus<u8>,
-    DC: OutputPin,
-    CS: OutputPin,
-    RST: OutputPin,
+    DI: Interface,
+    RST: OutputPin<Error = DI::PinError>,
+    BL: Backlight<DI::PinError>,
+    PF: PixelFormat,
 {
-    /// Creates a new driver instance that uses hardware SPI.
+    /// Returns the configuration of the connected panel.
+    pub fn panel(&self) -> &PanelConfig {
+        &self.config.panel
+    }
+
+    /// Returns the current orientation of the display.
+    pub fn orientation(&self) -> Orientation {
+        self.config.orientation
+    }
+
+    /// Returns the mirroring applied on top of the orientation.
+    pub fn mirror(&self) -> Mirror {
+        self.config.mirror
+    }
+
+    /// Returns the color order of the panel.
+    pub fn color_order(&self) -> ColorOrder {
+        self.config.panel.color_order
+    }
+
+    /// Releases the interface, the reset and TE pins and the backlight.
+    pub fn release(self) -> (DI, RST, TE, BL) {
+        (self.interface, self.rst, self.te, self.backlight)
+    }
+
+    /// Changes the color order of the panel.
+    ///
+    /// This sets or clears the BGR bit of MADCTL, so the controller swaps the red and
+    /// blue channel of all pixels written afterwards. Pixels already in the frame memory
+    /// are not affected.
     ///
     /// # Arguments
     ///
-    /// * `spi` - SPI interface.
-    /// * `dc` - Data/command pin.
-    /// * `rst` - Reset pin.
-    /// * `rgb` - Whether the display is RGB (true) or BGR (false).
-    /// * `width` - Width of the display.
-    /// * `height` - Height of the display.
-    pub fn new(
-        spi: SPI,
-        dc: DC,
-        cs: CS,
-        rst: RST,
-        _rgb: bool,
-        sd: u16,
-        width: u32,
-        height: u32,
-    ) -> Self {
-        ST7789V2 {
-            spi,
-            dc,
-            cs,
-            rst,
-            _rgb,
-            sd,
-            width,
-            height,
-            regions: [None; 10],
-        }
+    /// * `color_order` - The new color order.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn set_color_order(
+        &mut self,
+        color_order: ColorOrder,
+    ) -> Result<(), Error<DI::SpiError, DI::PinError>> {
+        self.config.panel.color_order = color_order;
+        self.write_madctl()
+    }
+
+    /// Changes the orientation of the display.
+    ///
+    /// This rewrites MADCTL, swaps width and height when switching between portrait and
+    /// landscape and recomputes the frame memory offsets. The frame memory is not redrawn,
+    /// so the caller should push a new frame afterwards.
+    ///
+    /// # Arguments
+    ///
+    /// * `orientation` - The new orientation.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn set_orientation(
+        &mut self,
+        orientation: Orientation,
+    ) -> Result<(), Error<DI::SpiError, DI::PinError>> {
+        self.config.orientation = orientation;
+        self.config.update_geometry();
+        self.write_madctl()
+    }
+
+    /// Changes the mirroring applied on top of the orientation.
+    ///
+    /// # Arguments
+    ///
+    /// * `mirror` - The new mirroring.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn set_mirror(&mut self, mirror: Mirror) -> Result<(), Error<DI::SpiError, DI::PinError>> {
+        self.config.mirror = mirror;
+        self.config.update_geometry();
+        self.write_madctl()
+    }
+
+    /// Sends MADCTL for the current orientation, mirroring and color order.
+    fn write_madctl(&mut self) -> Result<(), Error<DI::SpiError, DI::PinError>> {
+        let madctl = self.config.madctl();
+        self.write_command(Instruction::MadCtl as u8, &[madctl])
     }
 
     /// Initializes the display.
     ///
     /// This function initializes the display by sending a sequence of commands and settings
-    /// to configure the display properly. It includes a hardware reset and various configuration
-    /// commands.
+    /// to configure the display properly. It includes a hardware reset, the configured
+    /// [`InitSequence`] and the commands derived from the driver configuration.
     ///
     /// # Arguments
     ///
@@ -258,51 +381,56 @@ where
     ///
     /// # Returns
     ///
-    /// `Result<(), ()>` indicating success or failure.
-    pub fn init<DELAY>(&mut self, delay: &mut DELAY) -> Result<(), ()>
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn init<DELAY>(
+        &mut self,
+        delay: &mut DELAY,
+    ) -> Result<(), Error<DI::SpiError, DI::PinError>>
     where
         DELAY: DelayNs,
     {
+        if self.config.backlight_deferred {
+            // Hide the random frame memory content until the first frame is pushed
+            self.backlight.set_percent(0)?;
+            self.config.backlight_pending = true;
+        }
         self.hard_reset(delay)?;
         //Set Attributes for Scan Direction
-        if self.sd == VERTICAL {
-            self.write_command(Instruction::MadCtl as u8, &[0x00])?; // Vertical
-        } else {
-            self.write_command(Instruction::MadCtl as u8, &[0x78])?; // Horizontal
-        }
+        self.write_madctl()?;
 
         //Initalize Display
-        //self.write_command(Instruction::MadCtl as u8, &[0x00])?;  //Vertical Screen Direction
-        self.write_command(Instruction::ColMod as u8, &[0x05])?;
-        self.write_command(0xB2, &[0x0B, 0x0B, 0x00, 0x33, 0x35])?;
-        self.write_command(0xB7, &[0x11])?;
-        self.write_command(0xBB, &[0x35])?;
-        self.write_command(0xC0, &[0x2C])?;
-        self.write_command(0xC2, &[0x01])?;
-        self.write_command(0xC3, &[0x0D])?;
-        self.write_command(0xC4, &[0x20])?;
-        self.write_command(0xC6, &[0x13])?;
-        self.write_command(0xD0, &[0xA4, 0xA1])?;
-        self.write_command(0xD6, &[0xA1])?;
-        self.write_command(
-            0xE0,
-            &[
-                0xF0, 0x06, 0x0B, 0x0A, 0x09, 0x26, 0x29, 0x33, 0x41, 0x18, 0x16, 0x15, 0x29, 0x2D,
-            ],
-        )?;
-        self.write_command(
-            0xE1,
-            &[
-                0xF0, 0x04, 0x08, 0x08, 0x07, 0x03, 0x28, 0x32, 0x40, 0x3B, 0x19, 0x18, 0x2A, 0x2E,
-            ],
-        )?;
-        self.write_command(0xE4, &[0x25, 0x00, 0x00])?;
-        self.write_command(Instruction::InvOn as u8, &[])?;
+        self.write_command(Instruction::ColMod as u8, &[PF::COLMOD])?;
+        for step in self.config.init_sequence.steps() {
+            self.write_command(step.command, step.params)?;
+            if step.delay_ms > 0 {
+                delay.delay_ms(step.delay_ms);
+            }
+        }
+        if self.config.panel.inverted {
+            self.write_command(Instruction::InvOn as u8, &[])?;
+        } else {
+            self.write_command(Instruction::InvOff as u8, &[])?;
+        }
+        if let Some(curve) = self.config.gamma_curve {
+            self.write_command(Instruction::GamSet as u8, &[curve.gamset()])?;
+        }
+        if let Some(frame_rate) = self.config.frame_rate {
+            self.write_command(Instruction::PorCtrl as u8, &frame_rate.porch())?;
+            self.write_command(Instruction::FrCtrl2 as u8, &[frame_rate.frctrl2()])?;
+        }
+        if let Some(brightness) = self.config.brightness {
+            self.write_command(Instruction::WrDisBV as u8, &[brightness])?;
+        }
         self.write_command(Instruction::SlpOut as u8, &[])?;
 
         delay.delay_ms(120);
 
         self.write_command(Instruction::DispOn as u8, &[])?; // Display ON (DISPON)
+        self.config.power = PowerState::default();
+
+        if !self.config.backlight_pending {
+            self.backlight_on()?;
+        }
 
         Ok(())
     }
@@ -318,16 +446,19 @@ where
     ///
     /// # Returns
     ///
-    /// `Result<(), ()>` indicating success or failure.
-    pub fn hard_reset<DELAY>(&mut self, delay: &mut DELAY) -> Result<(), ()>
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn hard_reset<DELAY>(
+        &mut self,
+        delay: &mut DELAY,
+    ) -> Result<(), Error<DI::SpiError, DI::PinError>>
     where
         DELAY: DelayNs,
     {
-        self.rst.set_high().map_err(|_| ())?;
+        self.rst.set_high().map_err(Error::Rst)?;
         delay.delay_ms(10);
-        self.rst.set_low().map_err(|_| ())?;
+        self.rst.set_low().map_err(Error::Rst)?;
         delay.delay_ms(10);
-        self.rst.set_high().map_err(|_| ())?;
+        self.rst.set_high().map_err(Error::Rst)?;
         delay.delay_ms(10);
 
         Ok(())
@@ -344,111 +475,147 @@ where
     ///
     /// # Returns
     ///
-    /// `Result<(), ()>` indicating success or failure.
-    fn write_command(&mut self, command: u8, params: &[u8]) -> Result<(), ()> {
-        self.cs.set_high().map_err(|_| ())?;
-        self.dc.set_low().map_err(|_| ())?;
-        self.cs.set_low().map_err(|_| ())?;
-        self.spi.write(&[command]).map_err(|_| ())?;
-        if !params.is_empty() {
-            self.start_data()?;
-            self.write_data(params)?;
-        }
-        self.cs.set_high().map_err(|_| ())?;
-        Ok(())
+    /// `Result<(), Error>` indicating success or failure.
+    fn write_command(
+        &mut self,
+        command: u8,
+        params: &[u8],
+    ) -> Result<(), Error<DI::SpiError, DI::PinError>> {
+        self.interface.write_command(command, params)
     }
 
-    /// Starts data transmission.
+    /// Writes data to the display.
     ///
-    /// Sets the data/command pin to indicate data mode for subsequent transmissions.
+    /// This function writes data following the last command through the interface.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - Data to write.
     ///
     /// # Returns
     ///
-    /// `Result<(), ()>` indicating success or failure.
-    fn start_data(&mut self) -> Result<(), ()> {
-        self.dc.set_high().map_err(|_| ())
+    /// `Result<(), Error>` indicating success or failure.
+    fn write_data(&mut self, data: &[u8]) -> Result<(), Error<DI::SpiError, DI::PinError>> {
+        self.interface.write_data(data)
     }
 
-    /// Writes data to the display.
+    /// Streams pixels to the display.
     ///
-    /// This function writes data to the display through the SPI interface.
+    /// The pixels are packed into a stack buffer which is sent whenever it is full. The
+    /// address window and RAMWR must have been set up by the caller.
     ///
     /// # Arguments
     ///
-    /// * `data` - Data to write.
+    /// * `pixels` - Colors of the pixels in address window order.
     ///
     /// # Returns
     ///
-    /// `Result<(), ()>` indicating success or failure.
-    fn write_data(&mut self, data: &[u8]) -> Result<(), ()> {
-        self.cs.set_high().map_err(|_| ())?;
-        self.dc.set_high().map_err(|_| ())?;
-        self.cs.set_low().map_err(|_| ())?;
-        self.spi.write(data).map_err(|_| ())?;
-        self.cs.set_high().map_err(|_| ())?;
-        Ok(())
+    /// The number of pixels written, or an error if writing failed.
+    fn write_pixels<I>(&mut self, pixels: I) -> Result<usize, Error<DI::SpiError, DI::PinError>>
+    where
+        I: IntoIterator<Item = PF::Color>,
+    {
+        let mut chunk = [0u8; CHUNK_SIZE];
+        let mut pixels = pixels.into_iter();
+        let mut written = 0;
+
+        loop {
+            let count = pack_chunk::<PF>(&mut chunk, &mut pixels);
+            if count == 0 {
+                return Ok(written);
+            }
+            self.write_data(&chunk[..PF::buffer_size(count)])?;
+            written += count;
+        }
     }
 
-    /// Writes a data word to the display.
+    /// Streams `count` pixels of the same color to the display.
     ///
-    /// This function writes a 16-bit word to the display.
+    /// The address window and RAMWR must have been set up by the caller.
     ///
     /// # Arguments
     ///
-    /// * `value` - Data word to write.
+    /// * `color` - Color of the pixels.
+    /// * `count` - Number of pixels to write.
     ///
     /// # Returns
     ///
-    /// `Result<(), ()>` indicating success or failure.
-    fn write_word(&mut self, value: u16) -> Result<(), ()> {
-        self.write_data(&value.to_be_bytes())
+    /// `Result<(), Error>` indicating success or failure.
+    fn write_repeated(
+        &mut self,
+        color: PF::Color,
+        count: usize,
+    ) -> Result<(), Error<DI::SpiError, DI::PinError>> {
+        // Fill the chunk once and send it as often as needed
+        let mut chunk = [0u8; CHUNK_SIZE];
+        let capacity = fill_chunk::<PF>(&mut chunk, color, count);
+
+        let mut remaining = count;
+        while remaining > 0 {
+            let pixels = remaining.min(capacity);
+            self.write_data(&chunk[..PF::buffer_size(pixels)])?;
+            remaining -= pixels;
+        }
+
+        Ok(())
     }
 
-    /// Sets the address window for the display.
+    /// Writes a run of pixels on one row.
     ///
-    /// This function sets the address window for subsequent drawing commands.
+    /// # Arguments
+    ///
+    /// * `start` - Coordinates of the first pixel of the run.
+    /// * `count` - Number of pixels in the run.
+    /// * `run` - Pixels of the run packed in the driver's pixel format.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    fn write_run(
+        &mut self,
+        start: (u16, u16),
+        count: usize,
+        run: &[u8],
+    ) -> Result<(), Error<DI::SpiError, DI::PinError>> {
+        let (x, y) = start;
+        self.set_address_window(x, y, x + count as u16 - 1, y)?;
+        self.write_data(&run[..PF::buffer_size(count)])
+    }
+
+    /// Sets the address window for the display and starts a memory write.
+    ///
+    /// This function sets the address window and sends RAMWR, so the pixel data of
+    /// subsequent drawing commands can be written right away.
     ///
     /// # Arguments
     ///
     /// * `start_x` - Start x-coordinate.
     /// * `start_y` - Start y-coordinate.
-    /// * `end_x` - End x-coordinate.
-    /// * `end_y` - End y-coordinate.
+    /// * `end_x` - End x-coordinate (inclusive).
+    /// * `end_y` - End y-coordinate (inclusive).
     ///
     /// # Returns
     ///
-    /// `Result<(), ()>` indicating success or failure.
+    /// `Result<(), Error>` indicating success or failure. `Error::OutOfBounds` is returned
+    /// if the end coordinates lie outside of the display.
     pub fn set_address_window(
         &mut self,
         start_x: u16,
         start_y: u16,
         end_x: u16,
         end_y: u16,
-    ) -> Result<(), ()> {
-        if self.sd == VERTICAL {
-            self.write_command(Instruction::CaSet as u8, &[])?;
-            self.start_data()?;
-            // Write start and end x-coordinates
-            self.write_data(&start_x.to_be_bytes())?; // Big-endian: splits into two bytes
-            self.write_data(&(end_x - 1).to_be_bytes())?;
-            self.write_command(Instruction::RaSet as u8, &[])?;
-            self.start_data()?;
-            // Write start and end y-coordinates (with a 20 pixel offset)
-            self.write_data(&(start_y + 20).to_be_bytes())?;
-            self.write_data(&(end_y + 20 - 1).to_be_bytes())?;
-        } else {
-            self.write_command(Instruction::CaSet as u8, &[])?;
-            self.start_data()?;
-            // Write start and end x-coordinates
-            self.write_data(&(start_x + 20).to_be_bytes())?; // Big-endian: splits into two bytes
-            self.write_data(&(end_x + 20 - 1).to_be_bytes())?;
-            self.write_command(Instruction::RaSet as u8, &[])?;
-            self.start_data()?;
-            // Write start and end y-coordinates (with a 20 pixel offset)
-            self.write_data(&(start_y).to_be_bytes())?;
-            self.write_data(&(end_y - 1).to_be_bytes())?;
-        }
-        self.write_command(0x2C, &[])?;
+    ) -> Result<(), Error<DI::SpiError, DI::PinError>> {
+        if self.config.power.sleeping {
+            return Err(Error::Sleeping);
+        }
+        if !self.config.contains(end_x, end_y) {
+            return Err(Error::OutOfBounds);
+        }
+
+        let (caset, raset) = self.config.address_window(start_x, start_y, end_x, end_y);
+        self.write_command(Instruction::CaSet as u8, &caset)?;
+        self.write_command(Instruction::RaSet as u8, &raset)?;
+        self.write_command(Instruction::RamWr as u8, &[])?;
 
         Ok(())
     }
@@ -460,44 +627,26 @@ where
     ///
     /// # Arguments
     ///
-    /// * `color` - The color to fill the screen with, in RGB565 format.
+    /// * `color` - The color to fill the screen with.
     ///
     /// # Returns
     ///
-    /// `Result<(), ()>` indicating success or failure.
-    pub fn clear_screen(&mut self, color: u16) -> Result<(), ()> {
-        let color_high = (color >> 8) as u8;
-        let color_low = (color & 0xff) as u8;
-
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn clear_screen(
+        &mut self,
+        color: PF::Color,
+    ) -> Result<(), Error<DI::SpiError, DI::PinError>> {
         // Set the address window to cover the entire screen
-        self.set_address_window(0, 0, self.width as u16 - 1, self.height as u16 - 1)?;
-        self.write_command(Instruction::RamWr as u8, &[])?;
-        self.start_data()?;
-
-        // Define a constant for the chunk size
-        const CHUNK_SIZE: usize = 512;
-        let mut chunk = [0u8; CHUNK_SIZE * 2];
-
-        // Fill the chunk with the color data
-        for i in 0..CHUNK_SIZE {
-            chunk[i * 2] = color_high;
-            chunk[i * 2 + 1] = color_low;
-        }
-
-        // Write data in chunks
-        let total_pixels = (self.width * self.height) as usize;
-        let full_chunks = total_pixels / CHUNK_SIZE;
-        let remaining_pixels = total_pixels % CHUNK_SIZE;
-
-        for _ in 0..full_chunks {
-            self.write_data(&chunk)?;
-        }
-
-        if remaining_pixels > 0 {
-            self.write_data(&chunk[0..(remaining_pixels * 2)])?;
-        }
+        self.set_address_window(
+            0,
+            0,
+            self.config.width as u16 - 1,
+            self.config.height as u16 - 1,
+        )?;
 
-        Ok(())
+        let total_pixels = self.config.pixels();
+        self.write_repeated(color, total_pixels)?;
+        self.frame_pushed()
     }
 
     /// Sets a pixel color at the given coordinates.
@@ -512,18 +661,29 @@ where
     ///
     /// # Returns
     ///
-    /// `Result<(), ()>` indicating success or failure.
-    pub fn write_pixel(&mut self, x: u16, y: u16, color: u16) -> Result<(), ()> {
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn write_pixel(
+        &mut self,
+        x: u16,
+        y: u16,
+        color: PF::Color,
+    ) -> Result<(), Error<DI::SpiError, DI::PinError>> {
+        if !self.config.contains(x, y) {
+            return Err(Error::OutOfBounds);
+        }
         self.set_address_window(x, y, x, y)?;
-        self.write_command(Instruction::RamWr as u8, &[])?;
-        self.start_data()?;
-        self.write_word(color)
+
+        let mut data = [0u8; 4];
+        PF::write_pixel(&mut data, 0, color);
+        self.write_data(&data[..PF::buffer_size(1)])?;
+        self.frame_pushed()
     }
 
-    /// Draws an image from a slice of RGB565 data.
+    /// Draws an image from a slice of raw pixel data.
     ///
-    /// This function draws an image from a slice of pixel data in RGB565 format.
-    /// It assumes the image dimensions match the display dimensions.
+    /// This function draws an image from a slice of pixel data packed in the driver's
+    /// pixel format. It assumes the image dimensions match the display dimensions; data
+    /// past the last pixel of the display is ignored.
     ///
     /// # Arguments
     ///
@@ -531,20 +691,29 @@ where
     ///
     /// # Returns
     ///
-    /// `Result<(), ()>` indicating success or failure.
-    pub fn draw_image(&mut self, image_data: &[u8]) -> Result<(), ()> {
-        let width = self.width as u16;
-        let height = self.height as u16;
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn draw_image(
+        &mut self,
+        image_data: &[u8],
+    ) -> Result<(), Error<DI::SpiError, DI::PinError>> {
+        let Some((_, _, end_x, end_y)) =
+            self.config
+                .clip_region(0, 0, self.config.width, self.config.height)
+        else {
+            return Ok(());
+        };
+        let size = PF::buffer_size(self.config.pixels_through(end_x, end_y));
+        if image_data.len() < size {
+            return Err(Error::BufferTooSmall);
+        }
 
-        self.set_address_window(0, 0, width - 1, height - 1)?;
-        self.write_command(Instruction::RamWr as u8, &[])?;
-        self.start_data()?;
+        self.set_address_window(0, 0, end_x, end_y)?;
 
-        for chunk in image_data.chunks(32) {
+        for chunk in image_data[..size].chunks(32) {
             self.write_data(chunk)?;
         }
 
-        Ok(())
+        self.frame_pushed()
     }
 
     /// Displays the provided buffer on the screen.
@@ -558,23 +727,19 @@ where
     ///
     /// # Returns
     ///
-    /// `Result<(), ()>` indicating success or failure.
-    pub fn show(&mut self, buffer: &[u8]) -> Result<(), ()> {
-        self.write_command(Instruction::CaSet as u8, &[])?;
-        self.write_data(&[0x00, 0x00, 0x00, 0xEF])?;
-
-        self.write_command(Instruction::RaSet as u8, &[])?;
-        self.write_data(&[0x00, 0x00, 0x00, 0xEF])?;
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn show(&mut self, buffer: &[u8]) -> Result<(), Error<DI::SpiError, DI::PinError>> {
+        if buffer.len() < PF::buffer_size(self.config.pixels()) {
+            return Err(Error::BufferTooSmall);
+        }
 
-        self.write_command(Instruction::RamWr as u8, &[])?;
+        let width = self.config.width as u16;
+        let height = self.config.height as u16;
 
-        self.cs.set_high().map_err(|_| ())?;
-        self.dc.set_high().map_err(|_| ())?;
-        self.cs.set_low().map_err(|_| ())?;
-        self.spi.write(buffer).map_err(|_| ())?;
-        self.cs.set_high().map_err(|_| ())?;
+        self.set_address_window(0, 0, width - 1, height - 1)?;
 
-        Ok(())
+        self.write_data(&buffer[..PF::buffer_size(self.config.pixels())])?;
+        self.frame_pushed()
     }
 
     /// Updates only the specified region of the display with the provided buffer.
@@ -583,9 +748,13 @@ where
     /// provided in the buffer. It calculates the necessary offsets and addresses to update only
     /// the designated area, ensuring efficient display refresh.
     ///
+    /// Parts of the region outside of the display are clipped, a region that lies completely
+    /// outside or has a zero size is ignored. The buffer holds the whole display, row by
+    /// row; it only has to extend up to the last pixel of the clipped region.
+    ///
     /// # Arguments
     ///
-    /// * `buffer` - A slice of bytes representing the pixel data in RGB565 format.
+    /// * `buffer` - A slice of bytes representing the pixel data in the driver's pixel format.
     /// * `top_left_x` - The x-coordinate of the top-left corner of the region to update.
     /// * `top_left_y` - The y-coordinate of the top-left corner of the region to update.
     /// * `width` - The width of the region to update.
@@ -593,7 +762,8 @@ where
     ///
     /// # Returns
     ///
-    /// `Result<(), ()>` indicating success (`Ok`) or failure (`Err`).
+    /// `Result<(), Error>` indicating success (`Ok`) or failure (`Err`). `Error::BufferTooSmall`
+    /// is returned if the buffer does not cover the region.
     pub fn show_region(
         &mut self,
         buffer: &[u8],
@@ -601,155 +771,232 @@ where
         top_left_y: u16,
         width: u32,
         height: u32,
-    ) -> Result<(), ()> {
-        let start_x = top_left_x as u16; // Start x-coordinate
-        let start_y = top_left_y as u16; // Start y-coordinate
-        let end_x = (top_left_x as u32 + width - 1) as u16; // End x-coordinate
-        let end_y = (top_left_y as u32 + height - 1) as u16; // End y-coordinate
-
-        // Calculate the buffer offset for the region
-        let buffer_width = self.width as usize; // Width of the buffer
-        let bytes_per_pixel = 2; // Number of bytes per pixel in RGB565 format
+    ) -> Result<(), Error<DI::SpiError, DI::PinError>> {
+        // Clip the region to the display, nothing is left to draw if it lies outside
+        let Some((start_x, start_y, end_x, end_y)) = self
+            .config
+            .clip_region(top_left_x, top_left_y, width, height)
+        else {
+            return Ok(());
+        };
+        if buffer.len() < PF::buffer_size(self.config.pixels_through(end_x, end_y)) {
+            return Err(Error::BufferTooSmall);
+        }
 
         // Set the address window for the region to be updated
         self.set_address_window(start_x, start_y, end_x, end_y)?;
 
-        // Send the command to write to RAM
-        self.write_command(Instruction::RamWr as u8, &[])?;
-
-        // Start data transmission
-        self.start_data()?;
-
-        // Iterate over each row in the region
-        for y in start_y..=end_y {
-            let start_index = ((y as usize) * buffer_width + (start_x as usize)) * bytes_per_pixel;
-            let end_index = start_index + (width as usize) * bytes_per_pixel;
-
-            // Write data to the display in chunks of 32 bytes
-            for chunk in buffer[start_index..end_index].chunks(32) {
-                self.write_data(chunk)?;
+        if let Some(rows) = self
+            .config
+            .window_rows::<PF>(start_x, start_y, end_x, end_y)
+        {
+            for row in rows {
+                self.write_data(&buffer[row])?;
             }
+        } else {
+            // Rows of sub-byte formats do not start on a byte boundary, so the pixels
+            // are repacked into one continuous stream.
+            let pixels = self
+                .config
+                .window_pixels(start_x, start_y, end_x, end_y)
+                .map(|index| PF::read_pixel(buffer, index));
+            self.write_pixels(pixels)?;
         }
 
-        Ok(())
-    }
-
-    pub fn store_region(&mut self, region: Region) -> Result<(), ()> {
-        for i in 0..self.regions.len() {
-            if self.regions[i].is_none() {
-                self.regions[i] = Some(region);
-                return Ok(());
-            }
-        }
-        Err(())
+        self.frame_pushed()
     }
 
-    pub fn store_region_from_params(
+    /// Updates the dirty regions of the display from the provided buffer.
+    ///
+    /// If the whole display is marked dirty, the buffer is shown completely.
+    ///
+    /// # Arguments
+    ///
+    /// * `buffer` - A slice of bytes representing the pixel data in the driver's pixel format.
+    /// * `regions` - The regions to update.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn show_regions<const N: usize>(
         &mut self,
-        x: u16,
-        y: u16,
-        width: u32,
-        height: u32,
-    ) -> Result<(), ()> {
-        let region = Region {
-            x,
-            y,
-            width,
-            height,
-        };
-
-        self.store_region(region)
-    }
-
-    pub fn get_regions(&self) -> &[Option<Region>] {
-        &self.regions
-    }
-
-    pub fn clear_regions(&mut self) {
-        self.regions = [None; 10];
-    }
-
-    pub fn show_regions(&mut self, buffer: &[u8]) -> Result<(), ()> {
-        for i in 0..self.regions.len() {
-            if self.regions[i].is_some() {
-                if let Some(region_data) = self.regions[i] {
-                    self.show_region(
-                        buffer,
-                        region_data.x,
-                        region_data.y,
-                        region_data.width,
-                        region_data.height,
-                    )?;
-                }
-            }
+        buffer: &[u8],
+        regions: &DirtyRegions<N>,
+    ) -> Result<(), Error<DI::SpiError, DI::PinError>> {
+        if regions.is_full_screen() {
+            return self.show(buffer);
+        }
+        for region in regions.regions() {
+            self.show_region(buffer, region.x, region.y, region.width, region.height)?;
         }
 
         Ok(())
     }
 
-    // Additional function with default parameter
-    pub fn show_regions_and_clear(&mut self, buffer: &[u8]) -> Result<(), ()> {
-        if let Err(e) = self.show_regions(buffer) {
-            // Handle the error, e.g., log it or return a different error
-            return Err(e);
-        }
-        self.clear_regions();
+    /// Updates the dirty regions of the display and clears them.
+    ///
+    /// # Arguments
+    ///
+    /// * `buffer` - A slice of bytes representing the pixel data in the driver's pixel format.
+    /// * `regions` - The regions to update, empty afterwards.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn show_regions_and_clear<const N: usize>(
+        &mut self,
+        buffer: &[u8],
+        regions: &mut DirtyRegions<N>,
+    ) -> Result<(), Error<DI::SpiError, DI::PinError>> {
+        self.show_regions(buffer, regions)?;
+        regions.clear();
         Ok(())
     }
 }
 
 // Implementing the DrawTarget trait for the ST7789V2 display driver
-impl<SPI, DC, CS, RST> DrawTarget for ST7789V2<SPI, DC, CS, RST>
+impl<DI, RST, PF, TE, BL> DrawTarget for ST7789V2<DI, RST, PF, TE, BL>
 where
-    SPI: SpiBus<u8>,
-    DC: OutputPin,
-    CS: OutputPin,
-    RST: OutputPin,
+    DI: Interface,
+    RST: OutputPin<Error = DI::PinError>,
+    BL: Backlight<DI::PinError>,
+    PF: PixelFormat,
 {
-    type Color = Rgb565;
-    type Error = ();
+    type Color = PF::Color;
+    type Error = Error<DI::SpiError, DI::PinError>;
 
     fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
     where
         I: IntoIterator<Item = Pixel<Self::Color>>,
     {
+        // Consecutive pixels on the same row are collected into a run and sent with
+        // a single address window.
+        let mut run = [0u8; RUN_SIZE];
+        let capacity = pixel_capacity::<PF>(RUN_SIZE);
+        let mut start = (0u16, 0u16);
+        let mut count = 0;
+        let mut drawn = false;
+
         for Pixel(coord, color) in pixels.into_iter() {
-            let color_value = color.into_storage();
             // Only draw pixels that would be on screen
-            if coord.x >= 0
-                && coord.y >= 0
-                && coord.x < self.width as i32
-                && coord.y < self.height as i32
+            if coord.x < 0
+                || coord.y < 0
+                || coord.x >= self.config.width as i32
+                || coord.y >= self.config.height as i32
             {
-                self.write_pixel(coord.x as u16, coord.y as u16, color_value)?;
+                continue;
             }
+            let (x, y) = (coord.x as u16, coord.y as u16);
+
+            let continues_run = count > 0
+                && count < capacity
+                && y == start.1
+                && x as usize == start.0 as usize + count;
+            if !continues_run {
+                if count > 0 {
+                    self.write_run(start, count, &run)?;
+                    drawn = true;
+                }
+                start = (x, y);
+                count = 0;
+            }
+
+            PF::write_pixel(&mut run, count, color);
+            count += 1;
+        }
+
+        if count > 0 {
+            self.write_run(start, count, &run)?;
+            drawn = true;
+        }
+        // Pixels that are all off screen leave the display untouched
+        if drawn {
+            self.frame_pushed()?;
         }
         Ok(())
     }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        let drawable = area.intersection(&self.bounding_box());
+        let Some(bottom_right) = drawable.bottom_right() else {
+            return Ok(());
+        };
+
+        self.set_address_window(
+            drawable.top_left.x as u16,
+            drawable.top_left.y as u16,
+            bottom_right.x as u16,
+            bottom_right.y as u16,
+        )?;
+
+        let written = if drawable == *area {
+            // The whole area is visible, so the colors map 1:1 to the window
+            let pixels = area.size.width as usize * area.size.height as usize;
+            self.write_pixels(colors.into_iter().take(pixels))?
+        } else {
+            // Skip the colors of pixels outside of the display
+            let pixels = area
+                .points()
+                .zip(colors)
+                .filter(|(point, _)| drawable.contains(*point))
+                .map(|(_, color)| color);
+            self.write_pixels(pixels)?
+        };
+
+        // The colors may run out before the first visible pixel
+        if written > 0 {
+            self.frame_pushed()?;
+        }
+        Ok(())
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.clear_screen(color)
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let drawable = area.intersection(&self.bounding_box());
+        let Some(bottom_right) = drawable.bottom_right() else {
+            return Ok(());
+        };
+
+        self.set_address_window(
+            drawable.top_left.x as u16,
+            drawable.top_left.y as u16,
+            bottom_right.x as u16,
+            bottom_right.y as u16,
+        )?;
+
+        let pixels = drawable.size.width as usize * drawable.size.height as usize;
+        self.write_repeated(color, pixels)?;
+        self.frame_pushed()
+    }
 }
 
 // Implementing the OriginDimensions trait for the ST7789V2 display driver
-impl<SPI, DC, CS, RST> OriginDimensions for ST7789V2<SPI, DC, CS, RST>
-where
-    SPI: SpiBus<u8>,
-    DC: OutputPin,
-    CS: OutputPin,
-    RST: OutputPin,
-{
+impl<DI, RST, PF, TE, BL> OriginDimensions for ST7789V2<DI, RST, PF, TE, BL> {
     fn size(&self) -> Size {
-        Size::new(self.width, self.height)
+        Size::new(self.config.width, self.config.height)
     }
 }
 
 /// A structure representing a frame buffer.
-pub struct FrameBuffer<'a> {
+///
+/// The pixels are packed in the [`PixelFormat`] `PF`, so the buffer can be passed to
+/// `show` and `show_region` of a driver using the same format.
+pub struct FrameBuffer<'a, PF = Rgb565Format> {
     buffer: &'a mut [u8],
     width: u32,
     height: u32,
+    color_order: ColorOrder,
+    _format: PhantomData<PF>,
 }
 
 impl<'a> FrameBuffer<'a> {
-    /// Creates a new frame buffer.
+    /// Creates a new RGB565 frame buffer.
     ///
     /// # Arguments
     ///
@@ -757,10 +1004,63 @@ impl<'a> FrameBuffer<'a> {
     /// * `width` - The width of the frame buffer.
     /// * `height` - The height of the frame buffer.
     pub fn new(buffer: &'a mut [u8], width: u32, height: u32) -> Self {
+        Self::with_format(buffer, width, height, Rgb565Format)
+    }
+}
+
+impl<'a, PF: PixelFormat> FrameBuffer<'a, PF> {
+    /// Creates a new frame buffer using the given pixel format.
+    ///
+    /// # Arguments
+    ///
+    /// * `buffer` - A mutable slice representing the pixel data.
+    /// * `width` - The width of the frame buffer.
+    /// * `height` - The height of the frame buffer.
+    /// * `_format` - The pixel format, e.g. `Rgb666Format`.
+    ///
+    /// The buffer should hold `width * height` pixels in the format. If it is shorter,
+    /// pixels past its end are ignored when drawing and clearing.
+    pub fn with_format(buffer: &'a mut [u8], width: u32, height: u32, _format: PF) -> Self {
         Self {
             buffer,
             width,
             height,
+            color_order: ColorOrder::Rgb,
+            _format: PhantomData,
+        }
+    }
+
+    /// Sets the channel order the pixels are stored in.
+    ///
+    /// With `ColorOrder::Bgr` the red and blue channel are swapped in software when
+    /// pixels are written and read. This is only needed for panels whose color order
+    /// is not corrected by the driver's MADCTL setting, e.g. when the same buffer is
+    /// pushed to RGB and BGR panels. Do not combine it with `ColorOrder::Bgr` on the
+    /// driver, or the channels are swapped twice.
+    ///
+    /// # Arguments
+    ///
+    /// * `color_order` - The channel order of the stored pixels.
+    pub fn set_color_order(&mut self, color_order: ColorOrder) {
+        self.color_order = color_order;
+    }
+
+    /// Returns the channel order the pixels are stored in.
+    pub fn color_order(&self) -> ColorOrder {
+        self.color_order
+    }
+
+    /// Returns the number of pixels of the frame buffer that fit into the buffer.
+    fn pixels(&self) -> usize {
+        let pixels = self.width as usize * self.height as usize;
+        pixels.min(self.buffer.len() * 8 / PF::BITS_PER_PIXEL)
+    }
+
+    /// Converts between the application's RGB colors and the stored channel order.
+    fn convert(&self, color: PF::Color) -> PF::Color {
+        match self.color_order {
+            ColorOrder::Rgb => color,
+            ColorOrder::Bgr => PF::swap_red_blue(color),
         }
     }
 
@@ -778,16 +1078,18 @@ impl<'a> FrameBuffer<'a> {
     /// # Arguments
     ///
     /// * `color` - The color to clear the buffer with.
-    pub fn clear(&mut self, color: Rgb565) {
-        let raw_color = color.into_storage();
-        for chunk in self.buffer.chunks_exact_mut(2) {
-            chunk[0] = (raw_color >> 8) as u8;
-            chunk[1] = raw_color as u8;
+    pub fn clear(&mut self, color: PF::Color) {
+        let color = self.convert(color);
+        for index in 0..self.pixels() {
+            PF::write_pixel(self.buffer, index, color);
         }
     }
 
     /// Copies a region from another buffer into this buffer.
     ///
+    /// The source buffer has the same dimensions as this buffer. Parts of the region
+    /// that lie outside of either buffer are skipped.
+    ///
     /// # Arguments
     ///
     /// * `src_buffer` - The source buffer.
@@ -797,6 +1099,7 @@ impl<'a> FrameBuffer<'a> {
     /// * `src_height` - The height of the source region.
     /// * `dest_x` - The x-coordinate of the top-left corner of the destination region.
     /// * `dest_y` - The y-coordinate of the top-left corner of the destination region.
+    #[allow(clippy::too_many_arguments)]
     pub fn copy_region(
         &mut self,
         src_buffer: &[u8],
@@ -807,17 +1110,45 @@ impl<'a> FrameBuffer<'a> {
         dest_x: u16,
         dest_y: u16,
     ) {
-        for row in 0..src_height as usize {
-            let src_row_start =
-                (src_y as usize + row) * self.width as usize * 2 + src_x as usize * 2;
-            let src_row_end = src_row_start + src_width as usize * 2;
+        let width = self.width as usize;
+        let height = self.height as usize;
+        let (src_x, src_y) = (src_x as usize, src_y as usize);
+        let (dest_x, dest_y) = (dest_x as usize, dest_y as usize);
+        if src_x.max(dest_x) >= width || src_y.max(dest_y) >= height {
+            return;
+        }
 
-            let dest_row_start =
-                (dest_y as usize + row) * self.width as usize * 2 + dest_x as usize * 2;
-            let dest_row_end = dest_row_start + src_width as usize * 2;
+        // Clip the region to the frame buffer, so rows never wrap into the next one
+        let columns = (src_width as usize).min(width - src_x).min(width - dest_x);
+        let rows = (src_height as usize)
+            .min(height - src_y)
+            .min(height - dest_y);
+        let src_pixels = src_buffer.len() * 8 / PF::BITS_PER_PIXEL;
+        let dest_pixels = self.pixels();
+        let src_width = columns as u32;
+
+        for row in 0..rows {
+            let src_start = (src_y + row) * width + src_x;
+            let dest_start = (dest_y + row) * width + dest_x;
+            if src_start + columns > src_pixels || dest_start + columns > dest_pixels {
+                break;
+            }
 
-            self.buffer[dest_row_start..dest_row_end]
-                .copy_from_slice(&src_buffer[src_row_start..src_row_end]);
+            if PF::BITS_PER_PIXEL % 8 == 0 {
+                let bytes_per_pixel = PF::BITS_PER_PIXEL / 8;
+                let src_row_start = src_start * bytes_per_pixel;
+                let src_row_end = src_row_start + src_width as usize * bytes_per_pixel;
+                let dest_row_start = dest_start * bytes_per_pixel;
+                let dest_row_end = dest_row_start + src_width as usize * bytes_per_pixel;
+
+                self.buffer[dest_row_start..dest_row_end]
+                    .copy_from_slice(&src_buffer[src_row_start..src_row_end]);
+            } else {
+                for i in 0..src_width as usize {
+                    let color = PF::read_pixel(src_buffer, src_start + i);
+                    PF::write_pixel(self.buffer, dest_start + i, color);
+                }
+            }
         }
     }
 
@@ -826,9 +1157,13 @@ impl<'a> FrameBuffer<'a> {
     /// # Arguments
     ///
     /// * `src_buffer` - The source buffer.
-    /// * `regions` - An array of regions to restore.
-    pub fn copy_regions(&mut self, src_buffer: &[u8], regions: &[Option<Region>]) {
-        for region in regions.iter().flatten() {
+    /// * `regions` - The regions to restore.
+    pub fn copy_regions<const N: usize>(&mut self, src_buffer: &[u8], regions: &DirtyRegions<N>) {
+        if regions.is_full_screen() {
+            self.copy_region(src_buffer, 0, 0, self.width, self.height, 0, 0);
+            return;
+        }
+        for region in regions.regions() {
             self.copy_region(
                 src_buffer,
                 region.x,
@@ -850,55 +1185,304 @@ impl<'a> FrameBuffer<'a> {
     ///
     /// # Returns
     ///
-    /// An iterator of `Pixel<Rgb565>`.
+    /// An iterator of `Pixel<PF::Color>`.
     pub fn diff_with<'b>(
         &'b self,
-        other: &'b FrameBuffer<'a>,
-    ) -> impl Iterator<Item = Pixel<Rgb565>> + 'b {
-        self.buffer
-            .chunks_exact(2)
-            .enumerate()
-            .filter_map(move |(i, chunk)| {
-                let other_chunk = &other.buffer[i * 2..i * 2 + 2];
-                if chunk != other_chunk {
-                    let x = (i as u32 % self.width) as i32;
-                    let y = (i as u32 / self.width) as i32;
-                    let raw_color = u16::from_be_bytes([chunk[0], chunk[1]]);
-                    let color = Rgb565::from(RawU16::new(raw_color));
-                    Some(Pixel(Point::new(x, y), color))
-                } else {
-                    None
-                }
-            })
+        other: &'b FrameBuffer<'a, PF>,
+    ) -> impl Iterator<Item = Pixel<PF::Color>> + 'b {
+        let pixels = self.pixels().min(other.pixels());
+        let width = self.width as usize;
+        (0..pixels).filter_map(move |i| {
+            let color = PF::read_pixel(self.buffer, i);
+            if color != PF::read_pixel(other.buffer, i) {
+                let x = (i % width) as i32;
+                let y = (i / width) as i32;
+                Some(Pixel(Point::new(x, y), self.convert(color)))
+            } else {
+                None
+            }
+        })
     }
 }
 
-impl<'a> DrawTarget for FrameBuffer<'a> {
-    type Color = Rgb565;
-    type Error = ();
+impl<'a, PF: PixelFormat> DrawTarget for FrameBuffer<'a, PF> {
+    type Color = PF::Color;
+    type Error = Infallible;
 
     fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
     where
         I: IntoIterator<Item = Pixel<Self::Color>>,
     {
+        let capacity = self.pixels();
         for Pixel(coord, color) in pixels {
             if coord.x >= 0
                 && coord.x < self.width as i32
                 && coord.y >= 0
                 && coord.y < self.height as i32
             {
-                let index = ((coord.y as u32 * self.width + coord.x as u32) * 2) as usize;
-                let raw_color = color.into_storage();
-                self.buffer[index] = (raw_color >> 8) as u8;
-                self.buffer[index + 1] = raw_color as u8;
+                let index = coord.y as usize * self.width as usize + coord.x as usize;
+                if index < capacity {
+                    PF::write_pixel(self.buffer, index, self.convert(color));
+                }
             }
         }
         Ok(())
     }
 }
 
-impl<'a> OriginDimensions for FrameBuffer<'a> {
+impl<'a, PF> OriginDimensions for FrameBuffer<'a, PF> {
     fn size(&self) -> Size {
         Size::new(self.width, self.height)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::mock::{display, MockInterface, MockPin};
+
+    fn display_240x280() -> ST7789V2<MockInterface, MockPin> {
+        display(PanelConfig::PANEL_240X280)
+    }
+
+    const FRAME: usize = 240 * 280 * 2;
+
+    #[test]
+    fn show_region_inside() {
+        let mut display = display_240x280();
+        display.show_region(&[0; FRAME], 10, 20, 30, 40).unwrap();
+        assert_eq!(display.interface.caset, [0, 10, 0, 39]);
+        // Rows are offset by 20 in the frame memory of the 240x280 panel
+        assert_eq!(display.interface.raset, [0, 40, 0, 79]);
+        assert_eq!(display.interface.data_bytes, 30 * 40 * 2);
+    }
+
+    #[test]
+    fn show_region_clipped_at_right_and_bottom_edge() {
+        let mut display = display_240x280();
+        display.show_region(&[0; FRAME], 230, 270, 50, 50).unwrap();
+        assert_eq!(display.interface.caset, [0, 230, 0, 239]);
+        assert_eq!(display.interface.raset, [1, 34, 1, 43]);
+        assert_eq!(display.interface.data_bytes, 10 * 10 * 2);
+    }
+
+    #[test]
+    fn show_region_outside_or_zero_size_is_ignored() {
+        let mut display = display_240x280();
+        display.show_region(&[0; FRAME], 240, 0, 10, 10).unwrap();
+        display.show_region(&[0; FRAME], 0, 280, 10, 10).unwrap();
+        display.show_region(&[0; FRAME], 10, 10, 0, 10).unwrap();
+        display.show_region(&[0; FRAME], 10, 10, 10, 0).unwrap();
+        display.show_region(&[], 0, 0, 0, 0).unwrap();
+        assert_eq!(display.interface.data_bytes, 0);
+    }
+
+    #[test]
+    fn show_region_does_not_overflow() {
+        let mut display = display_240x280();
+        display
+            .show_region(&[0; FRAME], 200, 0, u32::MAX, 1)
+            .unwrap();
+        assert_eq!(display.interface.caset, [0, 200, 0, 239]);
+        assert_eq!(display.interface.data_bytes, 40 * 2);
+    }
+
+    #[test]
+    fn show_region_buffer_only_has_to_reach_the_region() {
+        let mut display = display_240x280();
+        // Region ends at the last pixel of the second row
+        let buffer = [0; 240 * 2 * 2];
+        display.show_region(&buffer, 0, 0, 240, 2).unwrap();
+        assert_eq!(
+            display.show_region(&buffer[..buffer.len() - 1], 0, 0, 240, 2),
+            Err(Error::BufferTooSmall)
+        );
+        assert_eq!(
+            display.show_region(&buffer, 0, 1, 10, 2),
+            Err(Error::BufferTooSmall)
+        );
+    }
+
+    #[test]
+    fn show_regions_pushes_merged_regions_once() {
+        let mut display = display_240x280();
+        let mut dirty = DirtyRegions::<4>::new();
+        dirty.add(Region {
+            x: 0,
+            y: 0,
+            width: 10,
+            height: 10,
+        });
+        dirty.add(Region {
+            x: 5,
+            y: 0,
+            width: 10,
+            height: 10,
+        });
+        display
+            .show_regions_and_clear(&[0; FRAME], &mut dirty)
+            .unwrap();
+        assert_eq!(display.interface.data_bytes, 15 * 10 * 2);
+        assert!(dirty.is_empty());
+
+        dirty.mark_full_screen();
+        display.show_regions(&[0; FRAME], &dirty).unwrap();
+        assert_eq!(display.interface.data_bytes, 15 * 10 * 2 + FRAME);
+    }
+
+    #[test]
+    fn drawing_turns_on_a_deferred_backlight() {
+        use embedded_graphics::pixelcolor::Rgb565;
+
+        let mut display = display_240x280();
+        display.config.backlight_pending = true;
+        display
+            .fill_solid(&Rectangle::new(Point::zero(), Size::new(4, 4)), Rgb565::RED)
+            .unwrap();
+        assert!(!display.config.backlight_pending);
+
+        display.config.backlight_pending = true;
+        Pixel(Point::new(1, 1), Rgb565::RED)
+            .draw(&mut display)
+            .unwrap();
+        assert!(!display.config.backlight_pending);
+    }
+
+    #[test]
+    fn drawing_off_screen_keeps_the_backlight_deferred() {
+        use embedded_graphics::pixelcolor::Rgb565;
+
+        let mut display = display_240x280();
+        display.config.backlight_pending = true;
+        display
+            .fill_solid(
+                &Rectangle::new(Point::new(-10, 0), Size::new(4, 4)),
+                Rgb565::RED,
+            )
+            .unwrap();
+        Pixel(Point::new(240, 0), Rgb565::RED)
+            .draw(&mut display)
+            .unwrap();
+        // The colors run out before the first visible pixel
+        display
+            .fill_contiguous(
+                &Rectangle::new(Point::new(-4, 0), Size::new(8, 1)),
+                [Rgb565::RED; 4],
+            )
+            .unwrap();
+        assert!(display.config.backlight_pending);
+        assert_eq!(display.interface.data_bytes, 0);
+    }
+
+    #[test]
+    fn draw_image_ignores_data_past_the_frame() {
+        let image = [0u8; FRAME + 100];
+        let mut display = display_240x280();
+        display.draw_image(&image).unwrap();
+        assert_eq!(display.interface.data_bytes, FRAME);
+        assert_eq!(
+            display.draw_image(&image[..FRAME - 1]),
+            Err(Error::BufferTooSmall)
+        );
+    }
+
+    #[test]
+    fn backlight_on_restores_the_level() {
+        let mut display = display_240x280();
+        display.set_backlight(30).unwrap();
+        display.backlight_off().unwrap();
+        assert_eq!(display.backlight(), 0);
+        display.backlight_on().unwrap();
+        assert_eq!(display.backlight(), 30);
+
+        display.set_backlight(0).unwrap();
+        display.backlight_on().unwrap();
+        assert_eq!(display.backlight(), 30);
+    }
+
+    #[test]
+    fn address_window_rejects_end_coordinates_off_screen() {
+        let mut display = display_240x280();
+        assert_eq!(
+            display.set_address_window(0, 0, 240, 0),
+            Err(Error::OutOfBounds)
+        );
+        assert_eq!(
+            display.set_address_window(0, 0, 0, 280),
+            Err(Error::OutOfBounds)
+        );
+        assert_eq!(display.interface.memory_writes, 0);
+
+        display.set_address_window(0, 0, 239, 279).unwrap();
+        assert_eq!(display.interface.caset, [0, 0, 0, 239]);
+        assert_eq!(display.interface.raset, [0, 20, 1, 43]);
+    }
+
+    #[test]
+    fn memory_write_is_started_once_per_window() {
+        use embedded_graphics::pixelcolor::Rgb565;
+
+        let mut display = display_240x280();
+        display
+            .fill_solid(&Rectangle::new(Point::zero(), Size::new(4, 4)), Rgb565::RED)
+            .unwrap();
+        assert_eq!(display.interface.memory_writes, 1);
+
+        // Two runs on separate rows
+        display
+            .draw_iter([
+                Pixel(Point::new(0, 0), Rgb565::RED),
+                Pixel(Point::new(1, 0), Rgb565::RED),
+                Pixel(Point::new(0, 1), Rgb565::RED),
+            ])
+            .unwrap();
+        assert_eq!(display.interface.memory_writes, 3);
+
+        display.show_region(&[0; FRAME], 0, 0, 10, 10).unwrap();
+        assert_eq!(display.interface.memory_writes, 4);
+    }
+
+    #[test]
+    fn frame_buffer_ignores_pixels_past_a_short_buffer() {
+        use embedded_graphics::pixelcolor::Rgb565;
+
+        let mut buffer = [0u8; 100];
+        let mut frame = FrameBuffer::new(&mut buffer, 240, 280);
+        frame.clear(Rgb565::WHITE);
+        Pixel(Point::new(100, 100), Rgb565::RED)
+            .draw(&mut frame)
+            .unwrap();
+        let mut other = [0u8; 40];
+        let other = FrameBuffer::new(&mut other, 240, 280);
+        assert_eq!(frame.diff_with(&other).count(), 20);
+        assert!(buffer.iter().all(|&byte| byte == 0xFF));
+    }
+
+    #[test]
+    fn copy_regions_clips_regions_past_the_edge() {
+        let mut source = [0u8; 240 * 20 * 2];
+        for (i, byte) in source.iter_mut().enumerate() {
+            *byte = (i / 2 % 240) as u8;
+        }
+        let mut buffer = [0xFFu8; 240 * 20 * 2];
+        let mut frame = FrameBuffer::new(&mut buffer, 240, 20);
+
+        let mut dirty = DirtyRegions::<4>::new();
+        dirty.add(Region {
+            x: 230,
+            y: 10,
+            width: 20,
+            height: 20,
+        });
+        frame.copy_regions(&source, &dirty);
+
+        for y in 0..20 {
+            for x in 0..240 {
+                let index = (y * 240 + x) * 2;
+                let expected = if x >= 230 && y >= 10 { x as u8 } else { 0xFF };
+                assert_eq!(buffer[index], expected, "pixel ({x}, {y})");
+            }
+        }
+    }
+}
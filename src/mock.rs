@@ -0,0 +1,146 @@
+//! Test doubles for the display interface and control pins.
+
+use core::convert::Infallible;
+use std::vec::Vec;
+
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::{ErrorType, OutputPin};
+
+use crate::{Error, Interface, PanelConfig, ReadInterface, ST7789V2Builder, ST7789V2};
+
+/// Interface recording the commands sent and answering reads from canned data.
+#[derive(Default)]
+pub(crate) struct MockInterface {
+    /// Parameters of the last CASET and RASET.
+    pub(crate) caset: [u8; 4],
+    pub(crate) raset: [u8; 4],
+    /// Number of RAMWR commands sent.
+    pub(crate) memory_writes: usize,
+    /// Number of pixel data bytes written.
+    pub(crate) data_bytes: usize,
+    /// Every command with its parameters, in order.
+    pub(crate) commands: Vec<(u8, Vec<u8>)>,
+    /// Bytes returned for a read command, missing bytes read as 0.
+    pub(crate) reads: Vec<(u8, Vec<u8>)>,
+}
+
+impl MockInterface {
+    /// Returns the parameters of the last time `command` was sent.
+    pub(crate) fn last(&self, command: u8) -> Option<&[u8]> {
+        self.commands
+            .iter()
+            .rev()
+            .find(|(sent, _)| *sent == command)
+            .map(|(_, params)| params.as_slice())
+    }
+}
+
+impl Interface for MockInterface {
+    type SpiError = ();
+    type PinError = Infallible;
+
+    fn write_command(&mut self, command: u8, params: &[u8]) -> Result<(), Error<(), Infallible>> {
+        match command {
+            0x2A => self.caset.copy_from_slice(params),
+            0x2B => self.raset.copy_from_slice(params),
+            0x2C => self.memory_writes += 1,
+            _ => {}
+        }
+        self.commands.push((command, params.to_vec()));
+        Ok(())
+    }
+
+    fn write_data(&mut self, data: &[u8]) -> Result<(), Error<(), Infallible>> {
+        self.data_bytes += data.len();
+        Ok(())
+    }
+}
+
+impl ReadInterface for MockInterface {
+    fn read_command(
+        &mut self,
+        command: u8,
+        buffer: &mut [u8],
+    ) -> Result<(), Error<(), Infallible>> {
+        self.commands.push((command, Vec::new()));
+        buffer.fill(0);
+        if let Some((_, data)) = self.reads.iter().find(|(read, _)| *read == command) {
+            let len = data.len().min(buffer.len());
+            buffer[..len].copy_from_slice(&data[..len]);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "async")]
+impl crate::AsyncInterface for MockInterface {
+    type SpiError = ();
+    type PinError = Infallible;
+
+    async fn write_command(
+        &mut self,
+        command: u8,
+        params: &[u8],
+    ) -> Result<(), Error<(), Infallible>> {
+        Interface::write_command(self, command, params)
+    }
+
+    async fn write_data(&mut self, data: &[u8]) -> Result<(), Error<(), Infallible>> {
+        Interface::write_data(self, data)
+    }
+}
+
+/// Control pin that is never driven in these tests.
+pub(crate) struct MockPin;
+
+impl ErrorType for MockPin {
+    type Error = Infallible;
+}
+
+impl OutputPin for MockPin {
+    fn set_low(&mut self) -> Result<(), Infallible> {
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Infallible> {
+        Ok(())
+    }
+}
+
+/// Delay returning at once.
+pub(crate) struct MockDelay;
+
+impl DelayNs for MockDelay {
+    fn delay_ns(&mut self, _ns: u32) {}
+}
+
+/// Creates a driver for the given panel on a mock interface.
+pub(crate) fn display(panel: PanelConfig) -> ST7789V2<MockInterface, MockPin> {
+    ST7789V2Builder::with_interface(MockInterface::default(), MockPin)
+        .panel(panel)
+        .build()
+        .unwrap()
+}
+
+/// Creates an async driver for the given panel on a mock interface.
+#[cfg(feature = "async")]
+pub(crate) fn display_async(panel: PanelConfig) -> crate::ST7789V2Async<MockInterface, MockPin> {
+    ST7789V2Builder::with_interface(MockInterface::default(), MockPin)
+        .panel(panel)
+        .build_async()
+        .unwrap()
+}
+
+/// Runs a future to completion, the mocks never have to wait.
+#[cfg(feature = "async")]
+pub(crate) fn block_on<F: core::future::Future>(future: F) -> F::Output {
+    use core::task::{Context, Poll, Waker};
+
+    let mut future = core::pin::pin!(future);
+    let mut context = Context::from_waker(Waker::noop());
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+            return output;
+        }
+    }
+}
@@ -0,0 +1,104 @@
+/// MADCTL row address order bit (MY).
+pub(crate) const MADCTL_MY: u8 = 0x80;
+/// MADCTL column address order bit (MX).
+pub(crate) const MADCTL_MX: u8 = 0x40;
+/// MADCTL row/column exchange bit (MV).
+pub(crate) const MADCTL_MV: u8 = 0x20;
+/// MADCTL RGB/BGR order bit.
+pub(crate) const MADCTL_BGR: u8 = 0x08;
+
+/// Orientation of the display content.
+///
+/// Each variant is a 90° step clockwise from the previous one, starting with the
+/// panel's native portrait orientation.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    /// Native orientation, connector at the bottom.
+    #[default]
+    Portrait,
+    /// Rotated 90° clockwise.
+    Landscape,
+    /// Rotated 180°.
+    PortraitFlipped,
+    /// Rotated 270° clockwise.
+    LandscapeFlipped,
+}
+
+impl Orientation {
+    /// Returns `true` if width and height are exchanged compared to the native panel.
+    pub fn is_landscape(self) -> bool {
+        matches!(self, Orientation::Landscape | Orientation::LandscapeFlipped)
+    }
+
+    /// Returns the MY, MX and MV bits of MADCTL for this orientation and mirroring.
+    pub(crate) fn madctl(self, mirror: Mirror) -> u8 {
+        let mut madctl = match self {
+            Orientation::Portrait => 0x00,
+            Orientation::Landscape => MADCTL_MX | MADCTL_MV,
+            Orientation::PortraitFlipped => MADCTL_MX | MADCTL_MY,
+            Orientation::LandscapeFlipped => MADCTL_MY | MADCTL_MV,
+        };
+
+        // With MV set the x axis runs along the rows of the frame memory, so the
+        // mirror bits have to be swapped as well.
+        let (x_bit, y_bit) = if self.is_landscape() {
+            (MADCTL_MY, MADCTL_MX)
+        } else {
+            (MADCTL_MX, MADCTL_MY)
+        };
+        if mirror.x {
+            madctl ^= x_bit;
+        }
+        if mirror.y {
+            madctl ^= y_bit;
+        }
+
+        madctl
+    }
+}
+
+/// Optional mirroring applied on top of an [`Orientation`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Mirror {
+    /// Mirror the x axis (left and right are swapped).
+    pub x: bool,
+    /// Mirror the y axis (top and bottom are swapped).
+    pub y: bool,
+}
+
+impl Mirror {
+    /// No mirroring.
+    pub const NONE: Mirror = Mirror { x: false, y: false };
+    /// Mirror along the x axis.
+    pub const X: Mirror = Mirror { x: true, y: false };
+    /// Mirror along the y axis.
+    pub const Y: Mirror = Mirror { x: false, y: true };
+    /// Mirror along both axes.
+    pub const XY: Mirror = Mirror { x: true, y: true };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn madctl_of_every_orientation_and_mirror() {
+        let mirrors = [Mirror::NONE, Mirror::X, Mirror::Y, Mirror::XY];
+        let table = [
+            (Orientation::Portrait, [0x00, 0x40, 0x80, 0xC0]),
+            // MV set: mirroring x flips the rows (MY), mirroring y the columns (MX)
+            (Orientation::Landscape, [0x60, 0xE0, 0x20, 0xA0]),
+            (Orientation::PortraitFlipped, [0xC0, 0x80, 0x40, 0x00]),
+            (Orientation::LandscapeFlipped, [0xA0, 0x20, 0xE0, 0x60]),
+        ];
+        for (orientation, expected) in table {
+            for (mirror, madctl) in mirrors.into_iter().zip(expected) {
+                assert_eq!(
+                    orientation.madctl(mirror),
+                    madctl,
+                    "{orientation:?} {mirror:?}"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,215 @@
+use crate::orientation::{Orientation, MADCTL_BGR, MADCTL_MV, MADCTL_MX, MADCTL_MY};
+
+/// Width of the ST7789V2 frame memory in pixels.
+pub(crate) const RAM_WIDTH: u16 = 240;
+/// Height of the ST7789V2 frame memory in pixels.
+pub(crate) const RAM_HEIGHT: u16 = 320;
+
+/// Order of the color channels on the panel.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ColorOrder {
+    /// Red, green, blue.
+    #[default]
+    Rgb,
+    /// Blue, green, red.
+    Bgr,
+}
+
+impl ColorOrder {
+    /// Returns the RGB/BGR bit of MADCTL for this color order.
+    pub(crate) fn madctl(self) -> u8 {
+        match self {
+            ColorOrder::Rgb => 0x00,
+            ColorOrder::Bgr => MADCTL_BGR,
+        }
+    }
+}
+
+/// Geometry and defaults of a specific ST7789 panel module.
+///
+/// The controller always has 240x320 pixels of frame memory, but most modules only
+/// show part of it. The offsets describe where the visible area sits in the frame
+/// memory in the native portrait orientation; the `_flipped` offsets are used when
+/// the corresponding axis is mirrored, which moves the visible area to the other end.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PanelConfig {
+    /// Visible width in the native portrait orientation.
+    pub width: u16,
+    /// Visible height in the native portrait orientation.
+    pub height: u16,
+    /// First visible column of the frame memory.
+    pub col_offset: u16,
+    /// First visible row of the frame memory.
+    pub row_offset: u16,
+    /// First visible column when the column order is reversed (MX set).
+    pub col_offset_flipped: u16,
+    /// First visible row when the row order is reversed (MY set).
+    pub row_offset_flipped: u16,
+    /// Whether the panel needs display inversion to show correct colors.
+    pub inverted: bool,
+    /// Order of the color channels on the panel.
+    pub color_order: ColorOrder,
+}
+
+impl PanelConfig {
+    /// 1.3" 240x240 module.
+    pub const PANEL_240X240: PanelConfig = PanelConfig::new(240, 240, 0, 0, 0, 80);
+    /// 1.69" 240x280 module, as used on the Waveshare RP2040-LCD-1.69.
+    pub const PANEL_240X280: PanelConfig = PanelConfig::new(240, 280, 0, 20, 0, 20);
+    /// 2.0" 240x320 module covering the whole frame memory.
+    pub const PANEL_240X320: PanelConfig = PanelConfig::new(240, 320, 0, 0, 0, 0);
+    /// 1.14" 135x240 module.
+    pub const PANEL_135X240: PanelConfig = PanelConfig::new(135, 240, 52, 40, 53, 40);
+    /// 1.47" 172x320 module.
+    pub const PANEL_172X320: PanelConfig = PanelConfig::new(172, 320, 34, 0, 34, 0);
+    /// 1.9" 170x320 module.
+    pub const PANEL_170X320: PanelConfig = PanelConfig::new(170, 320, 35, 0, 35, 0);
+
+    /// Creates a panel configuration with inversion enabled and RGB color order,
+    /// which is what the common ST7789 IPS modules need.
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - Visible width in the native portrait orientation.
+    /// * `height` - Visible height in the native portrait orientation.
+    /// * `col_offset` - First visible column of the frame memory.
+    /// * `row_offset` - First visible row of the frame memory.
+    /// * `col_offset_flipped` - First visible column when MX is set.
+    /// * `row_offset_flipped` - First visible row when MY is set.
+    pub const fn new(
+        width: u16,
+        height: u16,
+        col_offset: u16,
+        row_offset: u16,
+        col_offset_flipped: u16,
+        row_offset_flipped: u16,
+    ) -> Self {
+        PanelConfig {
+            width,
+            height,
+            col_offset,
+            row_offset,
+            col_offset_flipped,
+            row_offset_flipped,
+            inverted: true,
+            color_order: ColorOrder::Rgb,
+        }
+    }
+
+    /// Returns a copy with the given inversion default.
+    pub const fn with_inverted(mut self, inverted: bool) -> Self {
+        self.inverted = inverted;
+        self
+    }
+
+    /// Returns a copy with the given color order.
+    pub const fn with_color_order(mut self, color_order: ColorOrder) -> Self {
+        self.color_order = color_order;
+        self
+    }
+
+    /// Returns `true` if the visible area fits into the frame memory in every orientation.
+    pub const fn is_valid(&self) -> bool {
+        self.width > 0
+            && self.height > 0
+            && self.width as u32 + self.col_offset as u32 <= RAM_WIDTH as u32
+            && self.width as u32 + self.col_offset_flipped as u32 <= RAM_WIDTH as u32
+            && self.height as u32 + self.row_offset as u32 <= RAM_HEIGHT as u32
+            && self.height as u32 + self.row_offset_flipped as u32 <= RAM_HEIGHT as u32
+    }
+
+    /// Returns the visible width and height in the given orientation.
+    pub fn size(&self, orientation: Orientation) -> (u16, u16) {
+        if orientation.is_landscape() {
+            (self.height, self.width)
+        } else {
+            (self.width, self.height)
+        }
+    }
+
+    /// Returns the x and y offset of the visible area for the given MADCTL value.
+    pub(crate) fn offsets(&self, madctl: u8) -> (u16, u16) {
+        let col_offset = if madctl & MADCTL_MX != 0 {
+            self.col_offset_flipped
+        } else {
+            self.col_offset
+        };
+        let row_offset = if madctl & MADCTL_MY != 0 {
+            self.row_offset_flipped
+        } else {
+            self.row_offset
+        };
+
+        // With MV set the x axis runs along the rows of the frame memory.
+        if madctl & MADCTL_MV != 0 {
+            (row_offset, col_offset)
+        } else {
+            (col_offset, row_offset)
+        }
+    }
+}
+
+impl Default for PanelConfig {
+    fn default() -> Self {
+        PanelConfig::PANEL_240X280
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Mirror;
+
+    fn offsets(panel: PanelConfig) -> [(u16, u16); 4] {
+        [
+            Orientation::Portrait,
+            Orientation::Landscape,
+            Orientation::PortraitFlipped,
+            Orientation::LandscapeFlipped,
+        ]
+        .map(|orientation| panel.offsets(orientation.madctl(Mirror::NONE)))
+    }
+
+    #[test]
+    fn offsets_follow_the_orientation() {
+        assert_eq!(
+            offsets(PanelConfig::PANEL_135X240),
+            [(52, 40), (40, 53), (53, 40), (40, 52)]
+        );
+        assert_eq!(
+            offsets(PanelConfig::PANEL_170X320),
+            [(35, 0), (0, 35), (35, 0), (0, 35)]
+        );
+        assert_eq!(
+            offsets(PanelConfig::PANEL_172X320),
+            [(34, 0), (0, 34), (34, 0), (0, 34)]
+        );
+        assert_eq!(
+            offsets(PanelConfig::PANEL_240X240),
+            [(0, 0), (0, 0), (0, 80), (80, 0)]
+        );
+    }
+
+    #[test]
+    fn mirroring_selects_the_flipped_offsets() {
+        let panel = PanelConfig::PANEL_135X240;
+        let madctl = Orientation::Portrait.madctl(Mirror::X);
+        assert_eq!(panel.offsets(madctl), (53, 40));
+        let madctl = Orientation::Landscape.madctl(Mirror::Y);
+        assert_eq!(panel.offsets(madctl), (40, 52));
+    }
+
+    #[test]
+    fn presets_fit_the_frame_memory() {
+        for panel in [
+            PanelConfig::PANEL_240X240,
+            PanelConfig::PANEL_240X280,
+            PanelConfig::PANEL_240X320,
+            PanelConfig::PANEL_135X240,
+            PanelConfig::PANEL_172X320,
+            PanelConfig::PANEL_170X320,
+        ] {
+            assert!(panel.is_valid(), "{panel:?}");
+        }
+    }
+}
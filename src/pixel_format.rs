@@ -0,0 +1,249 @@
+use embedded_graphics::pixelcolor::raw::RawU16;
+use embedded_graphics::pixelcolor::{Rgb565, Rgb666, Rgb888, RgbColor};
+use embedded_graphics::prelude::IntoStorage;
+
+/// Pixel format used on the interface between the MCU and the controller.
+///
+/// The format decides the COLMOD value sent by `init`, the color type accepted by the
+/// `DrawTarget` implementations and how pixels are packed into bytes. Pixels are packed
+/// MSB first without padding, so a buffer of `n` pixels takes
+/// `(n * BITS_PER_PIXEL).div_ceil(8)` bytes.
+pub trait PixelFormat {
+    /// Color type drawn in this format.
+    type Color: RgbColor;
+
+    /// Control interface color format written to COLMOD.
+    const COLMOD: u8;
+
+    /// Number of bits one pixel takes on the interface.
+    const BITS_PER_PIXEL: usize;
+
+    /// Writes `color` as the pixel with the given index into `buffer`.
+    fn write_pixel(buffer: &mut [u8], index: usize, color: Self::Color);
+
+    /// Reads the pixel with the given index from `buffer`.
+    fn read_pixel(buffer: &[u8], index: usize) -> Self::Color;
+
+    /// Returns `color` with the red and blue channel exchanged.
+    fn swap_red_blue(color: Self::Color) -> Self::Color;
+
+    /// Returns the number of bytes needed to hold `pixels` pixels.
+    fn buffer_size(pixels: usize) -> usize {
+        (pixels * Self::BITS_PER_PIXEL).div_ceil(8)
+    }
+}
+
+/// 12-bit RGB444, two pixels packed into three bytes.
+///
+/// Drawn with `Rgb565` colors, which are truncated to 4 bits per channel.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Rgb444Format;
+
+/// 16-bit RGB565, the controller's default format.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Rgb565Format;
+
+/// 18-bit RGB666, one byte per channel with the color in the upper 6 bits.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Rgb666Format;
+
+/// 18-bit interface format drawn with `Rgb888` colors.
+///
+/// The bytes are sent unchanged and the controller ignores the lower 2 bits of every
+/// channel, so 24-bit image data can be pushed without conversion.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Rgb888Format;
+
+impl PixelFormat for Rgb444Format {
+    type Color = Rgb565;
+    const COLMOD: u8 = 0x03;
+    const BITS_PER_PIXEL: usize = 12;
+
+    fn write_pixel(buffer: &mut [u8], index: usize, color: Rgb565) {
+        let r = color.r() >> 1;
+        let g = color.g() >> 2;
+        let b = color.b() >> 1;
+        let offset = index * 3 / 2;
+
+        // Even pixels start on a byte boundary, odd pixels in the middle of a byte.
+        if index.is_multiple_of(2) {
+            buffer[offset] = (r << 4) | g;
+            buffer[offset + 1] = (b << 4) | (buffer[offset + 1] & 0x0F);
+        } else {
+            buffer[offset] = (buffer[offset] & 0xF0) | r;
+            buffer[offset + 1] = (g << 4) | b;
+        }
+    }
+
+    fn read_pixel(buffer: &[u8], index: usize) -> Rgb565 {
+        let offset = index * 3 / 2;
+        let (r, g, b) = if index.is_multiple_of(2) {
+            (
+                buffer[offset] >> 4,
+                buffer[offset] & 0x0F,
+                buffer[offset + 1] >> 4,
+            )
+        } else {
+            (
+                buffer[offset] & 0x0F,
+                buffer[offset + 1] >> 4,
+                buffer[offset + 1] & 0x0F,
+            )
+        };
+
+        // Replicate the upper bits so that 0xF maps to full intensity.
+        Rgb565::new(
+            (r << 1) | (r >> 3),
+            (g << 2) | (g >> 2),
+            (b << 1) | (b >> 3),
+        )
+    }
+
+    fn swap_red_blue(color: Rgb565) -> Rgb565 {
+        Rgb565::new(color.b(), color.g(), color.r())
+    }
+}
+
+impl PixelFormat for Rgb565Format {
+    type Color = Rgb565;
+    const COLMOD: u8 = 0x05;
+    const BITS_PER_PIXEL: usize = 16;
+
+    fn write_pixel(buffer: &mut [u8], index: usize, color: Rgb565) {
+        let raw_color = color.into_storage();
+        buffer[index * 2] = (raw_color >> 8) as u8;
+        buffer[index * 2 + 1] = raw_color as u8;
+    }
+
+    fn read_pixel(buffer: &[u8], index: usize) -> Rgb565 {
+        let raw_color = u16::from_be_bytes([buffer[index * 2], buffer[index * 2 + 1]]);
+        Rgb565::from(RawU16::new(raw_color))
+    }
+
+    fn swap_red_blue(color: Rgb565) -> Rgb565 {
+        Rgb565::new(color.b(), color.g(), color.r())
+    }
+}
+
+impl PixelFormat for Rgb666Format {
+    type Color = Rgb666;
+    const COLMOD: u8 = 0x06;
+    const BITS_PER_PIXEL: usize = 24;
+
+    fn write_pixel(buffer: &mut [u8], index: usize, color: Rgb666) {
+        buffer[index * 3] = color.r() << 2;
+        buffer[index * 3 + 1] = color.g() << 2;
+        buffer[index * 3 + 2] = color.b() << 2;
+    }
+
+    fn read_pixel(buffer: &[u8], index: usize) -> Rgb666 {
+        Rgb666::new(
+            buffer[index * 3] >> 2,
+            buffer[index * 3 + 1] >> 2,
+            buffer[index * 3 + 2] >> 2,
+        )
+    }
+
+    fn swap_red_blue(color: Rgb666) -> Rgb666 {
+        Rgb666::new(color.b(), color.g(), color.r())
+    }
+}
+
+impl PixelFormat for Rgb888Format {
+    type Color = Rgb888;
+    const COLMOD: u8 = 0x06;
+    const BITS_PER_PIXEL: usize = 24;
+
+    fn write_pixel(buffer: &mut [u8], index: usize, color: Rgb888) {
+        buffer[index * 3] = color.r();
+        buffer[index * 3 + 1] = color.g();
+        buffer[index * 3 + 2] = color.b();
+    }
+
+    fn read_pixel(buffer: &[u8], index: usize) -> Rgb888 {
+        Rgb888::new(
+            buffer[index * 3],
+            buffer[index * 3 + 1],
+            buffer[index * 3 + 2],
+        )
+    }
+
+    fn swap_red_blue(color: Rgb888) -> Rgb888 {
+        Rgb888::new(color.b(), color.g(), color.r())
+    }
+}
+
+/// Size of the stack buffer used to stream pixels to the display.
+pub(crate) const CHUNK_SIZE: usize = 1024;
+
+/// Size of the stack buffer used to collect runs of pixels in `draw_iter`.
+pub(crate) const RUN_SIZE: usize = 192;
+
+/// Returns how many pixels of the format fit into a buffer of `bytes` bytes.
+///
+/// The count is kept even so that every chunk of a 12-bit format ends on a byte boundary.
+pub(crate) fn pixel_capacity<PF: PixelFormat>(bytes: usize) -> usize {
+    (bytes * 8 / PF::BITS_PER_PIXEL) & !1
+}
+
+/// Returns how many pixels of the format fit into one chunk.
+pub(crate) fn chunk_capacity<PF: PixelFormat>() -> usize {
+    pixel_capacity::<PF>(CHUNK_SIZE)
+}
+
+/// Packs pixels into a chunk until it is full or the pixels run out.
+///
+/// Returns the number of pixels packed, 0 once all pixels have been taken.
+pub(crate) fn pack_chunk<PF: PixelFormat>(
+    chunk: &mut [u8; CHUNK_SIZE],
+    pixels: &mut impl Iterator<Item = PF::Color>,
+) -> usize {
+    let mut count = 0;
+    for color in pixels.take(chunk_capacity::<PF>()) {
+        PF::write_pixel(chunk, count, color);
+        count += 1;
+    }
+    count
+}
+
+/// Fills a chunk with pixels of one color for a run of `count` pixels.
+///
+/// Returns the number of pixels in the chunk, at most `count`.
+pub(crate) fn fill_chunk<PF: PixelFormat>(
+    chunk: &mut [u8; CHUNK_SIZE],
+    color: PF::Color,
+    count: usize,
+) -> usize {
+    let pixels = chunk_capacity::<PF>().min(count);
+    for i in 0..pixels {
+        PF::write_pixel(chunk, i, color);
+    }
+    pixels
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rgb444_round_trip_on_odd_and_even_indices() {
+        // Colors with 4 bits per channel survive the round trip unchanged
+        let colors = [
+            Rgb565::RED,
+            Rgb565::GREEN,
+            Rgb565::BLUE,
+            Rgb565::new(10, 38, 25),
+        ];
+        let mut buffer = [0u8; 6];
+        // Odd pixels first, so the even ones have to keep their neighbours' nibbles
+        for index in [1, 3, 0, 2] {
+            Rgb444Format::write_pixel(&mut buffer, index, colors[index]);
+        }
+        assert_eq!(buffer, [0xF0, 0x00, 0xF0, 0x00, 0xF5, 0x9C]);
+        for (index, color) in colors.into_iter().enumerate() {
+            assert_eq!(Rgb444Format::read_pixel(&buffer, index), color, "{index}");
+        }
+        assert_eq!(Rgb444Format::buffer_size(4), 6);
+        assert_eq!(Rgb444Format::buffer_size(3), 5);
+    }
+}
@@ -0,0 +1,205 @@
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::OutputPin;
+
+use crate::{Backlight, Error, Instruction, Interface, PixelFormat, ST7789V2};
+
+/// Power modes of the controller as set through the driver.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PowerState {
+    /// Sleep mode, the panel is off and the frame memory cannot be written.
+    pub sleeping: bool,
+    /// The panel shows the frame memory.
+    pub display_on: bool,
+    /// Idle mode with 8 colors.
+    pub idle: bool,
+    /// Partial mode, only part of the rows are driven.
+    pub partial: bool,
+}
+
+impl Default for PowerState {
+    fn default() -> Self {
+        PowerState {
+            sleeping: false,
+            display_on: true,
+            idle: false,
+            partial: false,
+        }
+    }
+}
+
+impl<DI, RST, PF, TE, BL> ST7789V2<DI, RST, PF, TE, BL>
+where
+    DI: Interface,
+    RST: OutputPin<Error = DI::PinError>,
+    BL: Backlight<DI::PinError>,
+    PF: PixelFormat,
+{
+    /// Enters partial mode.
+    ///
+    /// Only the rows from `start_row` to `end_row` are driven, the rest of the panel
+    /// shows the non-display color (black on most modules). Rows are counted as seen in
+    /// `Orientation::Portrait`. The frame memory outside of the area keeps its content.
+    ///
+    /// # Arguments
+    ///
+    /// * `start_row` - First row of the partial area.
+    /// * `end_row` - Last row of the partial area (inclusive).
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure. `Error::OutOfBounds` is returned
+    /// if the rows lie outside of the panel or `start_row` is after `end_row`.
+    pub fn enter_partial_mode(
+        &mut self,
+        start_row: u16,
+        end_row: u16,
+    ) -> Result<(), Error<DI::SpiError, DI::PinError>> {
+        let panel = self.config.panel;
+        if start_row > end_row || end_row >= panel.height {
+            return Err(Error::OutOfBounds);
+        }
+
+        // PTLAR addresses frame memory rows, which start at the offset of the orientation
+        let row_offset = self.config.row_offset();
+        let [ps_hi, ps_lo] = (row_offset + start_row).to_be_bytes();
+        let [pe_hi, pe_lo] = (row_offset + end_row).to_be_bytes();
+        self.write_command(Instruction::PtlAr as u8, &[ps_hi, ps_lo, pe_hi, pe_lo])?;
+        self.write_command(Instruction::PtlOn as u8, &[])?;
+        self.config.power.partial = true;
+        Ok(())
+    }
+
+    /// Leaves partial mode and drives the whole panel again.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn exit_partial_mode(&mut self) -> Result<(), Error<DI::SpiError, DI::PinError>> {
+        self.write_command(Instruction::NorOn as u8, &[])?;
+        self.config.power.partial = false;
+        Ok(())
+    }
+
+    /// Returns the power modes the controller has been switched to.
+    pub fn power_state(&self) -> PowerState {
+        self.config.power
+    }
+
+    /// Enters sleep mode.
+    ///
+    /// The panel and the booster are turned off, the frame memory keeps its content.
+    /// Drawing returns `Error::Sleeping` until `wake` is called.
+    ///
+    /// The controller needs 120 ms between SLPIN and the next SLPOUT, so this waits the
+    /// full 120 ms before returning and `wake` can be called right away.
+    ///
+    /// # Arguments
+    ///
+    /// * `delay` - Delay provider.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn sleep<DELAY>(
+        &mut self,
+        delay: &mut DELAY,
+    ) -> Result<(), Error<DI::SpiError, DI::PinError>>
+    where
+        DELAY: DelayNs,
+    {
+        self.write_command(Instruction::SlpIn as u8, &[])?;
+        self.config.power.sleeping = true;
+        // The controller accepts commands again after 5ms, but SLPOUT has to wait 120ms
+        delay.delay_ms(120);
+        Ok(())
+    }
+
+    /// Leaves sleep mode.
+    ///
+    /// # Arguments
+    ///
+    /// * `delay` - Delay provider.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn wake<DELAY>(
+        &mut self,
+        delay: &mut DELAY,
+    ) -> Result<(), Error<DI::SpiError, DI::PinError>>
+    where
+        DELAY: DelayNs,
+    {
+        self.write_command(Instruction::SlpOut as u8, &[])?;
+        // The supply voltages and the oscillator need 120ms to settle, sleep mode must
+        // not be entered again before
+        delay.delay_ms(120);
+        self.config.power.sleeping = false;
+        Ok(())
+    }
+
+    /// Turns the panel off, the frame memory can still be written.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn display_off(&mut self) -> Result<(), Error<DI::SpiError, DI::PinError>> {
+        self.write_command(Instruction::DispOff as u8, &[])?;
+        self.config.power.display_on = false;
+        Ok(())
+    }
+
+    /// Turns the panel on again.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn display_on(&mut self) -> Result<(), Error<DI::SpiError, DI::PinError>> {
+        self.write_command(Instruction::DispOn as u8, &[])?;
+        self.config.power.display_on = true;
+        Ok(())
+    }
+
+    /// Enters or leaves idle mode.
+    ///
+    /// In idle mode only 8 colors are shown, using the most significant bit of every
+    /// channel, which lowers the power consumption.
+    ///
+    /// # Arguments
+    ///
+    /// * `idle` - Whether idle mode is enabled.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn set_idle_mode(&mut self, idle: bool) -> Result<(), Error<DI::SpiError, DI::PinError>> {
+        let command = if idle {
+            Instruction::IdmOn
+        } else {
+            Instruction::IdmOff
+        };
+        self.write_command(command as u8, &[])?;
+        self.config.power.idle = idle;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mock::display;
+    use crate::{Orientation, PanelConfig};
+
+    #[test]
+    fn partial_area_uses_the_row_offset_of_the_orientation() {
+        let mut display = display(PanelConfig::PANEL_240X240);
+        display.enter_partial_mode(10, 99).unwrap();
+        assert_eq!(display.interface.last(0x30), Some(&[0, 10, 0, 99][..]));
+
+        // With MY set the visible rows start at 320 - 240 = 80
+        display
+            .set_orientation(Orientation::PortraitFlipped)
+            .unwrap();
+        display.enter_partial_mode(10, 99).unwrap();
+        assert_eq!(display.interface.last(0x30), Some(&[0, 90, 0, 179][..]));
+    }
+}
@@ -0,0 +1,407 @@
+use embedded_hal::digital::OutputPin;
+
+use crate::{Backlight, Error, Instruction, PixelFormat, ReadInterface, Region, ST7789V2};
+
+/// Number of pixels read per RAMRD/RDMEMC transfer.
+const READ_PIXELS: usize = 64;
+
+/// Identification of the display module read with RDDID.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DisplayId {
+    /// Manufacturer ID of the module.
+    pub manufacturer: u8,
+    /// Module/driver version ID.
+    pub version: u8,
+    /// Module/driver ID.
+    pub driver: u8,
+}
+
+/// Memory access control settings read with RDDMADCTL.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Madctl {
+    /// MY: rows are addressed bottom to top.
+    pub row_address_order: bool,
+    /// MX: columns are addressed right to left.
+    pub column_address_order: bool,
+    /// MV: rows and columns are exchanged.
+    pub row_column_exchange: bool,
+    /// ML: the panel is refreshed bottom to top.
+    pub vertical_refresh_order: bool,
+    /// Red and blue are exchanged (BGR order).
+    pub bgr: bool,
+    /// MH: the panel is refreshed right to left.
+    pub horizontal_refresh_order: bool,
+}
+
+impl Madctl {
+    /// Decodes the MADCTL register value.
+    fn from_bits(bits: u8) -> Self {
+        Madctl {
+            row_address_order: bits & 0x80 != 0,
+            column_address_order: bits & 0x40 != 0,
+            row_column_exchange: bits & 0x20 != 0,
+            vertical_refresh_order: bits & 0x10 != 0,
+            bgr: bits & 0x08 != 0,
+            horizontal_refresh_order: bits & 0x04 != 0,
+        }
+    }
+}
+
+/// Power mode read with RDDPM.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PowerMode {
+    /// The booster voltage is on.
+    pub booster_on: bool,
+    /// Idle mode is on.
+    pub idle: bool,
+    /// Partial mode is on.
+    pub partial: bool,
+    /// Sleep mode is off.
+    pub sleep_out: bool,
+    /// Normal mode is on.
+    pub normal: bool,
+    /// The display is on.
+    pub display_on: bool,
+}
+
+/// Display status read with RDDST.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DisplayStatus {
+    /// The booster voltage is on.
+    pub booster_on: bool,
+    /// Memory access control settings.
+    pub madctl: Madctl,
+    /// Control interface color format (the lower 3 bits of COLMOD).
+    pub pixel_format: u8,
+    /// Idle mode is on.
+    pub idle: bool,
+    /// Partial mode is on.
+    pub partial: bool,
+    /// Sleep mode is off.
+    pub sleep_out: bool,
+    /// Normal mode is on.
+    pub normal: bool,
+    /// Vertical scrolling is on.
+    pub vertical_scrolling: bool,
+    /// Display inversion is on.
+    pub inverted: bool,
+    /// The display is on.
+    pub display_on: bool,
+    /// The tearing effect output is on.
+    pub tearing_effect_on: bool,
+    /// Selected gamma curve (GC0 = 0 to GC3 = 3).
+    pub gamma_curve: u8,
+    /// The tearing effect output includes the horizontal blanking.
+    pub tearing_effect_horizontal: bool,
+}
+
+impl<DI, RST, PF, TE, BL> ST7789V2<DI, RST, PF, TE, BL>
+where
+    DI: ReadInterface,
+    RST: OutputPin<Error = DI::PinError>,
+    BL: Backlight<DI::PinError>,
+    PF: PixelFormat,
+{
+    /// Reads data returned after a dummy clock cycle.
+    ///
+    /// Some commands send one dummy bit before the data. One more byte than needed is
+    /// read and the data is shifted back into place.
+    ///
+    /// # Arguments
+    ///
+    /// * `command` - Command to read.
+    /// * `data` - Buffer the data is written into, up to 4 bytes.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub(crate) fn read_after_dummy(
+        &mut self,
+        command: u8,
+        data: &mut [u8],
+    ) -> Result<(), Error<DI::SpiError, DI::PinError>> {
+        let mut raw = [0u8; 5];
+        let raw = &mut raw[..data.len() + 1];
+        self.interface.read_command(command, raw)?;
+        for (i, byte) in data.iter_mut().enumerate() {
+            *byte = (raw[i] << 1) | (raw[i + 1] >> 7);
+        }
+        Ok(())
+    }
+
+    /// Reads a single byte register.
+    fn read_byte(&mut self, command: u8) -> Result<u8, Error<DI::SpiError, DI::PinError>> {
+        let mut data = [0u8; 1];
+        self.interface.read_command(command, &mut data)?;
+        Ok(data[0])
+    }
+
+    /// Reads the identification of the display module.
+    ///
+    /// # Returns
+    ///
+    /// The decoded IDs, or an error if reading failed.
+    pub fn read_display_id(&mut self) -> Result<DisplayId, Error<DI::SpiError, DI::PinError>> {
+        let mut data = [0u8; 3];
+        self.read_after_dummy(Instruction::RddId as u8, &mut data)?;
+        Ok(DisplayId {
+            manufacturer: data[0],
+            version: data[1],
+            driver: data[2],
+        })
+    }
+
+    /// Reads the display status.
+    ///
+    /// # Returns
+    ///
+    /// The decoded status, or an error if reading failed.
+    pub fn read_status(&mut self) -> Result<DisplayStatus, Error<DI::SpiError, DI::PinError>> {
+        let mut data = [0u8; 4];
+        self.read_after_dummy(Instruction::RddSt as u8, &mut data)?;
+        let bits = u32::from_be_bytes(data);
+        let bit = |n: u32| bits & (1 << n) != 0;
+
+        Ok(DisplayStatus {
+            booster_on: bit(31),
+            // D30 to D25 hold MY, MX, MV, ML, RGB and MH in MADCTL order
+            madctl: Madctl::from_bits((bits >> 23) as u8 & 0xFC),
+            pixel_format: (bits >> 20) as u8 & 0x07,
+            idle: bit(19),
+            partial: bit(18),
+            sleep_out: bit(17),
+            normal: bit(16),
+            vertical_scrolling: bit(15),
+            inverted: bit(13),
+            display_on: bit(10),
+            tearing_effect_on: bit(9),
+            gamma_curve: (bits >> 6) as u8 & 0x07,
+            tearing_effect_horizontal: bit(5),
+        })
+    }
+
+    /// Reads the power mode.
+    ///
+    /// # Returns
+    ///
+    /// The decoded power mode, or an error if reading failed.
+    pub fn read_power_mode(&mut self) -> Result<PowerMode, Error<DI::SpiError, DI::PinError>> {
+        let bits = self.read_byte(Instruction::RdDPm as u8)?;
+        Ok(PowerMode {
+            booster_on: bits & 0x80 != 0,
+            idle: bits & 0x40 != 0,
+            partial: bits & 0x20 != 0,
+            sleep_out: bits & 0x10 != 0,
+            normal: bits & 0x08 != 0,
+            display_on: bits & 0x04 != 0,
+        })
+    }
+
+    /// Reads the memory access control settings.
+    ///
+    /// # Returns
+    ///
+    /// The decoded MADCTL value, or an error if reading failed.
+    pub fn read_madctl(&mut self) -> Result<Madctl, Error<DI::SpiError, DI::PinError>> {
+        let bits = self.read_byte(Instruction::RdDMadCtl as u8)?;
+        Ok(Madctl::from_bits(bits))
+    }
+
+    /// Reads the display brightness.
+    ///
+    /// # Returns
+    ///
+    /// The brightness (0 = lowest, 255 = highest), or an error if reading failed.
+    pub fn read_brightness(&mut self) -> Result<u8, Error<DI::SpiError, DI::PinError>> {
+        self.read_byte(Instruction::RdDisBV as u8)
+    }
+
+    /// Reads a region of the frame memory back as RGB565.
+    ///
+    /// The controller returns every pixel as 18-bit RGB666 in three bytes, independent
+    /// of the interface pixel format. The pixels are converted to big-endian RGB565 and
+    /// stored row by row in `buffer`, e.g. to take a screenshot of the live panel. The
+    /// region is read in chunks with RAMRD followed by RDMEMC, so no buffer of the size
+    /// of the region is needed on the stack.
+    ///
+    /// # Arguments
+    ///
+    /// * `region` - Region of the display to read.
+    /// * `buffer` - Buffer of at least `width * height * 2` bytes the pixels are written to.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn read_region(
+        &mut self,
+        region: Region,
+        buffer: &mut [u8],
+    ) -> Result<(), Error<DI::SpiError, DI::PinError>> {
+        if region.width == 0 || region.height == 0 {
+            return Ok(());
+        }
+        if region.x as u64 + region.width as u64 > self.config.width as u64
+            || region.y as u64 + region.height as u64 > self.config.height as u64
+        {
+            return Err(Error::OutOfBounds);
+        }
+        let pixels = (region.width * region.height) as usize;
+        if buffer.len() < pixels * 2 {
+            return Err(Error::BufferTooSmall);
+        }
+
+        let end_x = (region.x as u32 + region.width - 1) as u16;
+        let end_y = (region.y as u32 + region.height - 1) as u16;
+        let (caset, raset) = self.config.address_window(region.x, region.y, end_x, end_y);
+        self.write_command(Instruction::CaSet as u8, &caset)?;
+        self.write_command(Instruction::RaSet as u8, &raset)?;
+
+        // One dummy byte is returned before the pixel data
+        let mut raw = [0u8; 1 + READ_PIXELS * 3];
+        let mut command = Instruction::RamRd as u8;
+        for chunk in buffer[..pixels * 2].chunks_mut(READ_PIXELS * 2) {
+            let count = chunk.len() / 2;
+            let raw = &mut raw[..1 + count * 3];
+            self.interface.read_command(command, raw)?;
+
+            for (out, rgb) in chunk.chunks_exact_mut(2).zip(raw[1..].chunks_exact(3)) {
+                // Color components are returned in the upper 6 bits of each byte
+                let color = (u16::from(rgb[0] & 0xF8) << 8)
+                    | (u16::from(rgb[1] & 0xFC) << 3)
+                    | u16::from(rgb[2] >> 3);
+                out.copy_from_slice(&color.to_be_bytes());
+            }
+            command = Instruction::RdMemC as u8;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::display;
+    use crate::PanelConfig;
+
+    #[test]
+    fn display_id_drops_the_dummy_bit() {
+        let mut display = display(PanelConfig::PANEL_240X280);
+        // A set dummy bit followed by 0x85 0x85 0x52
+        display
+            .interface
+            .reads
+            .push((0x04, [0xC2, 0xC2, 0xA9, 0x00].to_vec()));
+        assert_eq!(
+            display.read_display_id(),
+            Ok(DisplayId {
+                manufacturer: 0x85,
+                version: 0x85,
+                driver: 0x52,
+            })
+        );
+    }
+
+    #[test]
+    fn status_is_decoded_after_the_dummy_bit() {
+        let mut display = display(PanelConfig::PANEL_240X280);
+        // 0xD4530480 shifted right by the dummy bit
+        display
+            .interface
+            .reads
+            .push((0x09, [0xEA, 0x29, 0x82, 0x40, 0x00].to_vec()));
+        assert_eq!(
+            display.read_status(),
+            Ok(DisplayStatus {
+                booster_on: true,
+                madctl: Madctl::from_bits(0x80 | 0x20 | 0x08),
+                pixel_format: 0x05,
+                idle: false,
+                partial: false,
+                sleep_out: true,
+                normal: true,
+                vertical_scrolling: false,
+                inverted: false,
+                display_on: true,
+                tearing_effect_on: false,
+                gamma_curve: 0x02,
+                tearing_effect_horizontal: false,
+            })
+        );
+    }
+
+    #[test]
+    fn power_mode_and_madctl_are_decoded() {
+        let mut display = display(PanelConfig::PANEL_240X280);
+        display.interface.reads.push((0x0A, [0x9C].to_vec()));
+        display.interface.reads.push((0x0B, [0x68].to_vec()));
+        assert_eq!(
+            display.read_power_mode(),
+            Ok(PowerMode {
+                booster_on: true,
+                idle: false,
+                partial: false,
+                sleep_out: true,
+                normal: true,
+                display_on: true,
+            })
+        );
+        assert_eq!(
+            display.read_madctl(),
+            Ok(Madctl {
+                row_address_order: false,
+                column_address_order: true,
+                row_column_exchange: true,
+                vertical_refresh_order: false,
+                bgr: true,
+                horizontal_refresh_order: false,
+            })
+        );
+    }
+
+    #[test]
+    fn read_region_rejects_overflowing_regions() {
+        let mut display = display(PanelConfig::PANEL_240X280);
+        let mut buffer = [0u8; 64];
+        let region = Region {
+            x: 10,
+            y: 0,
+            width: u32::MAX,
+            height: 1,
+        };
+        assert_eq!(
+            display.read_region(region, &mut buffer),
+            Err(Error::OutOfBounds)
+        );
+        let region = Region {
+            x: 0,
+            y: 10,
+            width: 1,
+            height: u32::MAX,
+        };
+        assert_eq!(
+            display.read_region(region, &mut buffer),
+            Err(Error::OutOfBounds)
+        );
+    }
+
+    #[test]
+    fn read_region_converts_rgb666_to_rgb565() {
+        let mut display = display(PanelConfig::PANEL_240X280);
+        // Dummy byte, then pure red and pure blue
+        display
+            .interface
+            .reads
+            .push((0x2E, [0x00, 0xFC, 0x00, 0x00, 0x00, 0x00, 0xFC].to_vec()));
+        let mut buffer = [0u8; 4];
+        let region = Region {
+            x: 0,
+            y: 0,
+            width: 2,
+            height: 1,
+        };
+        display.read_region(region, &mut buffer).unwrap();
+        assert_eq!(buffer, [0xF8, 0x00, 0x00, 0x1F]);
+        assert_eq!(display.interface.last(0x2A), Some(&[0, 0, 0, 1][..]));
+    }
+}
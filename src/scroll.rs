@@ -0,0 +1,202 @@
+use embedded_hal::digital::OutputPin;
+
+use crate::panel::RAM_HEIGHT;
+use crate::{Backlight, Error, Instruction, Interface, Mirror, Orientation, PixelFormat, ST7789V2};
+
+impl<DI, RST, PF, TE, BL> ST7789V2<DI, RST, PF, TE, BL>
+where
+    DI: Interface,
+    RST: OutputPin<Error = DI::PinError>,
+    BL: Backlight<DI::PinError>,
+    PF: PixelFormat,
+{
+    /// Defines the vertical scrolling area.
+    ///
+    /// The panel rows are split into a fixed area at the top, the scrolling area and a
+    /// fixed area at the bottom, counted in panel rows as seen in `Orientation::Portrait`.
+    /// Scrolling moves along the frame memory rows, so in the landscape orientations
+    /// the content scrolls horizontally. The scroll offset is reset to 0.
+    ///
+    /// # Arguments
+    ///
+    /// * `top_fixed` - Number of rows of the fixed area at the top.
+    /// * `scroll_height` - Number of rows of the scrolling area.
+    /// * `bottom_fixed` - Number of rows of the fixed area at the bottom.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure. `Error::InvalidGeometry` is
+    /// returned if the three areas do not add up to the height of the panel.
+    pub fn define_scroll_area(
+        &mut self,
+        top_fixed: u16,
+        scroll_height: u16,
+        bottom_fixed: u16,
+    ) -> Result<(), Error<DI::SpiError, DI::PinError>> {
+        let panel = self.config.panel;
+        if top_fixed as u32 + scroll_height as u32 + bottom_fixed as u32 != panel.height as u32 {
+            return Err(Error::InvalidGeometry);
+        }
+
+        // The frame memory rows hidden above and below the panel belong to the fixed areas
+        let top = self.config.row_offset() + top_fixed;
+        let bottom = RAM_HEIGHT - top - scroll_height;
+        let [tfa_hi, tfa_lo] = top.to_be_bytes();
+        let [vsa_hi, vsa_lo] = scroll_height.to_be_bytes();
+        let [bfa_hi, bfa_lo] = bottom.to_be_bytes();
+        self.write_command(
+            Instruction::VScrDef as u8,
+            &[tfa_hi, tfa_lo, vsa_hi, vsa_lo, bfa_hi, bfa_lo],
+        )?;
+
+        self.config.scroll_top = top_fixed;
+        self.config.scroll_height = scroll_height;
+        self.set_scroll_offset(0)
+    }
+
+    /// Scrolls the content of the scrolling area.
+    ///
+    /// The frame memory row `line` of the scrolling area is shown at its top, the rows
+    /// above it wrap around to the bottom.
+    ///
+    /// # Arguments
+    ///
+    /// * `line` - Offset within the scrolling area, smaller than its height.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure. `Error::OutOfBounds` is returned
+    /// if `line` lies outside of the scrolling area or no area has been defined.
+    pub fn set_scroll_offset(
+        &mut self,
+        line: u16,
+    ) -> Result<(), Error<DI::SpiError, DI::PinError>> {
+        if line >= self.config.scroll_height {
+            return Err(Error::OutOfBounds);
+        }
+
+        let address = self.config.row_offset() + self.config.scroll_top + line;
+        self.write_command(Instruction::VScSAd as u8, &address.to_be_bytes())?;
+        self.config.scroll_offset = line;
+        Ok(())
+    }
+
+    /// Returns the current offset within the scrolling area.
+    pub fn scroll_offset(&self) -> u16 {
+        self.config.scroll_offset
+    }
+
+    /// Scrolls the content up and writes the rows exposed at the bottom.
+    ///
+    /// The rows leaving the scrolling area at the top are reused for the new rows, so
+    /// only `lines` rows are transferred instead of the whole area. The new rows are
+    /// written along the display rows, so this only works in `Orientation::Portrait`
+    /// without mirroring.
+    ///
+    /// # Arguments
+    ///
+    /// * `rows` - Pixel data of the new rows in the driver's pixel format, one display width per row.
+    /// * `lines` - Number of rows to scroll by.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure. `Error::Unsupported` is returned
+    /// in any other orientation or with mirroring.
+    pub fn scroll_in(
+        &mut self,
+        rows: &[u8],
+        lines: u16,
+    ) -> Result<(), Error<DI::SpiError, DI::PinError>> {
+        if self.config.orientation != Orientation::Portrait || self.config.mirror != Mirror::NONE {
+            return Err(Error::Unsupported);
+        }
+        let height = self.config.scroll_height;
+        if lines == 0 {
+            return Ok(());
+        }
+        if lines > height {
+            return Err(Error::OutOfBounds);
+        }
+        let width = self.config.width as usize;
+        if rows.len() < PF::buffer_size(width * lines as usize) {
+            return Err(Error::BufferTooSmall);
+        }
+
+        // The rows at the current offset are the ones scrolled out at the top. They
+        // wrap around at the end of the scrolling area, so at most two windows are needed.
+        let offset = self.config.scroll_offset;
+        let before_wrap = lines.min(height - offset);
+        let segments = [
+            (offset, before_wrap, 0),
+            (0, lines - before_wrap, before_wrap),
+        ];
+        for (row, count, index) in segments {
+            if count == 0 {
+                continue;
+            }
+            let y = self.config.scroll_top + row;
+            self.set_address_window(0, y, width as u16 - 1, y + count - 1)?;
+
+            let start = width * index as usize;
+            let end = start + width * count as usize;
+            if (start * PF::BITS_PER_PIXEL).is_multiple_of(8) {
+                self.write_data(&rows[start * PF::BITS_PER_PIXEL / 8..PF::buffer_size(end)])?;
+            } else {
+                // Rows of 12-bit formats may start in the middle of a byte
+                self.write_pixels((start..end).map(|index| PF::read_pixel(rows, index)))?;
+            }
+        }
+
+        self.set_scroll_offset((offset + lines) % height)?;
+        self.frame_pushed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::display;
+    use crate::PanelConfig;
+
+    #[test]
+    fn scroll_area_uses_the_row_offset_of_the_orientation() {
+        let mut display = display(PanelConfig::PANEL_240X240);
+        display.define_scroll_area(10, 200, 30).unwrap();
+        // TFA = 0 + 10, VSA = 200, BFA = 320 - 210
+        assert_eq!(
+            display.interface.last(0x33),
+            Some(&[0, 10, 0, 200, 0, 110][..])
+        );
+        display.set_scroll_offset(5).unwrap();
+        assert_eq!(display.interface.last(0x37), Some(&[0, 15][..]));
+
+        // With MY set the visible rows start at 320 - 240 = 80
+        display
+            .set_orientation(Orientation::PortraitFlipped)
+            .unwrap();
+        display.define_scroll_area(10, 200, 30).unwrap();
+        assert_eq!(
+            display.interface.last(0x33),
+            Some(&[0, 90, 0, 200, 0, 30][..])
+        );
+        display.set_scroll_offset(5).unwrap();
+        assert_eq!(display.interface.last(0x37), Some(&[0, 95][..]));
+    }
+
+    #[test]
+    fn scroll_in_requires_portrait_without_mirroring() {
+        let rows = [0u8; 240 * 2 * 2];
+        let mut display = display(PanelConfig::PANEL_240X320);
+        display.define_scroll_area(0, 320, 0).unwrap();
+
+        display.set_orientation(Orientation::Landscape).unwrap();
+        assert_eq!(display.scroll_in(&rows, 2), Err(Error::Unsupported));
+        display.set_orientation(Orientation::Portrait).unwrap();
+        display.set_mirror(Mirror::Y).unwrap();
+        assert_eq!(display.scroll_in(&rows, 2), Err(Error::Unsupported));
+
+        display.set_mirror(Mirror::NONE).unwrap();
+        display.scroll_in(&rows, 2).unwrap();
+        assert_eq!(display.scroll_offset(), 2);
+    }
+}
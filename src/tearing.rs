@@ -0,0 +1,175 @@
+use embedded_hal::digital::{InputPin, OutputPin};
+
+use crate::panel::RAM_HEIGHT;
+use crate::{
+    Backlight, DirtyRegions, Error, Instruction, Interface, PixelFormat, ReadInterface, ST7789V2,
+};
+
+/// Output mode of the tearing effect (TE) signal.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TearingEffect {
+    /// TE output disabled.
+    #[default]
+    Off,
+    /// TE pulses during the vertical blanking interval.
+    Vertical,
+    /// TE pulses during the vertical and every horizontal blanking interval.
+    HorizontalAndVertical,
+}
+
+impl TearingEffect {
+    /// Returns the command and its parameters selecting this mode.
+    pub(crate) fn command(self) -> (u8, &'static [u8]) {
+        match self {
+            TearingEffect::Off => (Instruction::TEOFF as u8, &[]),
+            TearingEffect::Vertical => (Instruction::TEON as u8, &[0x00]),
+            TearingEffect::HorizontalAndVertical => (Instruction::TEON as u8, &[0x01]),
+        }
+    }
+}
+
+impl<DI, RST, PF, TE, BL> ST7789V2<DI, RST, PF, TE, BL>
+where
+    DI: Interface,
+    RST: OutputPin<Error = DI::PinError>,
+    BL: Backlight<DI::PinError>,
+    PF: PixelFormat,
+{
+    /// Configures the tearing effect output of the controller.
+    ///
+    /// # Arguments
+    ///
+    /// * `mode` - The new TE output mode.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn set_tearing_effect(
+        &mut self,
+        mode: TearingEffect,
+    ) -> Result<(), Error<DI::SpiError, DI::PinError>> {
+        let (command, params) = mode.command();
+        self.write_command(command, params)
+    }
+
+    /// Sets the scanline at which the TE signal is raised.
+    ///
+    /// Pushing a frame from an earlier line than the one being scanned out avoids
+    /// tearing when the transfer takes longer than the blanking interval.
+    ///
+    /// # Arguments
+    ///
+    /// * `line` - Scanline in the frame memory (0 to 319).
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn set_tear_scanline(
+        &mut self,
+        line: u16,
+    ) -> Result<(), Error<DI::SpiError, DI::PinError>> {
+        self.write_command(Instruction::Ste as u8, &line.to_be_bytes())
+    }
+}
+
+impl<DI, RST, PF, TE, BL> ST7789V2<DI, RST, PF, TE, BL>
+where
+    DI: Interface,
+    RST: OutputPin<Error = DI::PinError>,
+    BL: Backlight<DI::PinError>,
+    TE: InputPin<Error = DI::PinError>,
+    PF: PixelFormat,
+{
+    /// Busy-waits for the next rising edge of the TE signal.
+    ///
+    /// The TE output has to be enabled with `set_tearing_effect` first.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn wait_for_te(&mut self) -> Result<(), Error<DI::SpiError, DI::PinError>> {
+        while self.te.is_high().map_err(Error::Te)? {}
+        while self.te.is_low().map_err(Error::Te)? {}
+        Ok(())
+    }
+
+    /// Waits for the TE signal and displays the provided buffer.
+    ///
+    /// # Arguments
+    ///
+    /// * `buffer` - Buffer to display.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn show_synced(&mut self, buffer: &[u8]) -> Result<(), Error<DI::SpiError, DI::PinError>> {
+        self.wait_for_te()?;
+        self.show(buffer)
+    }
+
+    /// Waits for the TE signal and updates the dirty regions from the provided buffer.
+    ///
+    /// # Arguments
+    ///
+    /// * `buffer` - A slice of bytes representing the pixel data in the driver's pixel format.
+    /// * `regions` - The regions to update.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn show_regions_synced<const N: usize>(
+        &mut self,
+        buffer: &[u8],
+        regions: &DirtyRegions<N>,
+    ) -> Result<(), Error<DI::SpiError, DI::PinError>> {
+        self.wait_for_te()?;
+        self.show_regions(buffer, regions)
+    }
+}
+
+impl<DI, RST, PF, TE, BL> ST7789V2<DI, RST, PF, TE, BL>
+where
+    DI: ReadInterface,
+    RST: OutputPin<Error = DI::PinError>,
+    BL: Backlight<DI::PinError>,
+    PF: PixelFormat,
+{
+    /// Reads the scanline the controller is currently refreshing.
+    ///
+    /// Lines are counted in the frame memory like for `set_tear_scanline`, so the
+    /// first visible row of a panel with a row offset is scanned at that offset.
+    ///
+    /// # Returns
+    ///
+    /// The current scanline, or an error if reading failed.
+    pub fn current_scanline(&mut self) -> Result<u16, Error<DI::SpiError, DI::PinError>> {
+        let mut data = [0u8; 2];
+        self.read_after_dummy(Instruction::GScan as u8, &mut data)?;
+        Ok(u16::from_be_bytes(data) & 0x03FF)
+    }
+
+    /// Busy-waits until the refresh has passed the given scanline.
+    ///
+    /// Without a TE pin, a region can be pushed without tearing by waiting until the
+    /// panel has been refreshed past its last row and writing it behind the refresh.
+    /// Returns immediately if the scanline has already been passed in this frame.
+    ///
+    /// # Arguments
+    ///
+    /// * `line` - Scanline in the frame memory (0 to 318).
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn wait_for_scanline_past(
+        &mut self,
+        line: u16,
+    ) -> Result<(), Error<DI::SpiError, DI::PinError>> {
+        // The last line is never passed before the next frame starts
+        if line >= RAM_HEIGHT - 1 {
+            return Err(Error::OutOfBounds);
+        }
+        while self.current_scanline()? <= line {}
+        Ok(())
+    }
+}